@@ -0,0 +1,210 @@
+use rodio::source::{SineWave, Source};
+use rodio::{Decoder, OutputStream, OutputStreamBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::Manager;
+use ts_rs::TS;
+
+use crate::clicker_state::{ClickerPhase, StopReason};
+use crate::logging::target;
+use crate::settings::{SettingsError, SharedSettings};
+use crate::supervisor::MutexExt;
+
+const TONE_LENGTH: Duration = Duration::from_millis(70);
+/// Gap between the two beeps of the alert tone.
+const ALERT_GAP: Duration = Duration::from_millis(80);
+const FADE_IN: Duration = Duration::from_millis(5);
+/// Built-in tones at full volume are much louder than typical sound files.
+const TONE_AMPLITUDE: f32 = 0.3;
+
+/// Something worth a sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum Cue {
+    ChannelOn,
+    ChannelOff,
+    /// The clicker was stopped by something other than the user.
+    Alert,
+}
+
+impl Cue {
+    /// The cue for a stop from `previous`, if it deserves one. Stopping a
+    /// clicker that wasn't running makes no sound.
+    pub fn for_stop(reason: StopReason, previous: ClickerPhase) -> Option<Cue> {
+        if !previous.is_running() {
+            return None;
+        }
+        match reason {
+            StopReason::AppExit => None,
+            StopReason::User if previous.channels().any() => Some(Cue::ChannelOff),
+            StopReason::User => None,
+            StopReason::EmergencyStop
+            | StopReason::Failsafe
+            | StopReason::UserTookOver
+            | StopReason::BackendError
+            | StopReason::WorkerCrashed => Some(Cue::Alert),
+        }
+    }
+
+    fn frequency(self) -> f32 {
+        match self {
+            Cue::ChannelOn => 880.0,
+            Cue::ChannelOff => 587.0,
+            Cue::Alert => 330.0,
+        }
+    }
+}
+
+/// Which sounds play and how loud. An empty file path uses the built-in tone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SoundSettings {
+    pub enabled: bool,
+    /// From 0 to 1.
+    pub volume: f64,
+    /// WAV or OGG file played when a channel turns on.
+    pub channel_on_file: String,
+    pub channel_off_file: String,
+    /// Played when a fail-safe, emergency stop or error stops the clicker.
+    pub alert_file: String,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            volume: 0.5,
+            channel_on_file: String::new(),
+            channel_off_file: String::new(),
+            alert_file: String::new(),
+        }
+    }
+}
+
+impl SoundSettings {
+    pub fn validate(&self, field: &'static str, errors: &mut Vec<SettingsError>) {
+        if !(0.0..=1.0).contains(&self.volume) {
+            errors.push(SettingsError {
+                field,
+                message: format!("volume must be 0-1, got {}", self.volume),
+            });
+        }
+    }
+
+    fn file(&self, cue: Cue) -> &str {
+        match cue {
+            Cue::ChannelOn => &self.channel_on_file,
+            Cue::ChannelOff => &self.channel_off_file,
+            Cue::Alert => &self.alert_file,
+        }
+    }
+}
+
+fn play_tone(stream: &OutputStream, cue: Cue, volume: f32) {
+    let beep = || {
+        SineWave::new(cue.frequency())
+            .take_duration(TONE_LENGTH)
+            .fade_in(FADE_IN)
+            .amplify(volume * TONE_AMPLITUDE)
+    };
+    if cue == Cue::Alert {
+        stream
+            .mixer()
+            .add(beep().mix(beep().delay(TONE_LENGTH + ALERT_GAP)));
+    } else {
+        stream.mixer().add(beep());
+    }
+}
+
+fn play(stream: &OutputStream, cue: Cue, settings: &SoundSettings) {
+    let volume = settings.volume as f32;
+    let path = settings.file(cue);
+    if path.is_empty() {
+        return play_tone(stream, cue, volume);
+    }
+    let decoded = File::open(path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| Decoder::try_from(file).map_err(|e| e.to_string()));
+    match decoded {
+        Ok(source) => stream.mixer().add(source.amplify(volume)),
+        Err(e) => {
+            log::warn!(
+                target: target::APP,
+                "Failed to play {}, using the built-in tone: {}",
+                path,
+                e
+            );
+            play_tone(stream, cue, volume);
+        }
+    }
+}
+
+/// Owns the output stream, which can't leave the thread that opened it on
+/// every platform.
+fn run_player(cues: Receiver<(Cue, SoundSettings)>) {
+    let mut stream = match OutputStreamBuilder::open_default_stream() {
+        Ok(stream) => stream,
+        Err(e) => {
+            log::warn!(target: target::APP, "No audio output for sound cues: {}", e);
+            return;
+        }
+    };
+    stream.log_on_drop(false);
+    for (cue, settings) in cues {
+        play(&stream, cue, &settings);
+    }
+}
+
+/// Plays sound cues on a background thread, started on the first cue so the
+/// audio device is only opened once sounds are turned on.
+#[derive(Default)]
+pub struct AudioCues {
+    player: Mutex<Option<Sender<(Cue, SoundSettings)>>>,
+}
+
+impl AudioCues {
+    /// Queues `cue` if sounds are enabled. Never blocks on audio.
+    pub fn play(&self, app_handle: &tauri::AppHandle, cue: Cue) {
+        let settings = app_handle.state::<SharedSettings>().snapshot().sounds;
+        if settings.enabled {
+            self.send(cue, settings);
+        }
+    }
+
+    /// Queues `cue` whether or not sounds are enabled, for trying them out.
+    pub fn preview(&self, app_handle: &tauri::AppHandle, cue: Cue) {
+        let settings = app_handle.state::<SharedSettings>().snapshot().sounds;
+        self.send(cue, settings);
+    }
+
+    fn send(&self, cue: Cue, settings: SoundSettings) {
+        let mut player = self.player.lock_unpoisoned();
+        // The player thread is gone if it couldn't open an output; try again.
+        if let Some(sender) = player.as_ref() {
+            if sender.send((cue, settings.clone())).is_ok() {
+                return;
+            }
+        }
+        let (sender, receiver) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name("audio".to_string())
+            .spawn(move || run_player(receiver));
+        match spawned {
+            Ok(_) => {
+                let _ = sender.send((cue, settings));
+                *player = Some(sender);
+            }
+            Err(e) => log::warn!(target: target::APP, "Failed to start the audio thread: {}", e),
+        }
+    }
+}
+
+/// Plays `cue` with the current sound settings, even if sounds are off.
+#[tauri::command]
+pub fn preview_cue(app_handle: tauri::AppHandle, cue: Cue) {
+    app_handle.state::<AudioCues>().preview(&app_handle, cue);
+}
@@ -0,0 +1,137 @@
+use enigo::{Enigo, Settings};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use ts_rs::TS;
+
+use crate::clicker::ClickerError;
+use crate::clicker_state::{ClickerState, StopReason};
+use crate::logging::target;
+use crate::supervisor::MutexExt;
+
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(500);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Whether the click thread has a working input backend.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase", tag = "state")]
+pub enum BackendStatus {
+    /// The first connection attempt hasn't finished yet.
+    #[default]
+    Connecting,
+    Ready,
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        error: String,
+        /// Failed attempts in a row.
+        attempts: u32,
+        #[ts(type = "number")]
+        retry_in_ms: u64,
+    },
+}
+
+/// Tracks the click thread's input backend, so the clicker can refuse to start
+/// without one instead of looking like it runs while nothing clicks.
+#[derive(Default)]
+pub struct InputBackend {
+    status: Mutex<BackendStatus>,
+}
+
+impl InputBackend {
+    pub fn status(&self) -> BackendStatus {
+        self.status.lock_unpoisoned().clone()
+    }
+
+    /// Errors once the backend is known not to work. Starting while the first
+    /// attempt is still running is allowed; a failure then stops the clicker.
+    pub fn ensure_usable(&self) -> Result<(), ClickerError> {
+        match self.status() {
+            BackendStatus::Failed { error, .. } => Err(ClickerError::NoBackend(error)),
+            _ => Ok(()),
+        }
+    }
+
+    fn set(&self, app_handle: &tauri::AppHandle, status: BackendStatus) {
+        *self.status.lock_unpoisoned() = status.clone();
+        if let BackendStatus::Failed { .. } = status {
+            app_handle.emit("backend-error", &status).unwrap_or_else(
+                |e| log::warn!(target: target::APP, "Failed to emit backend-error: {}", e),
+            );
+            let state = app_handle.state::<ClickerState>();
+            if state.is_running() {
+                state.stop(StopReason::BackendError);
+            }
+        }
+        app_handle.emit("backend-status", status).unwrap_or_else(
+            |e| log::warn!(target: target::APP, "Failed to emit backend-status: {}", e),
+        );
+    }
+
+    /// Creates the click thread's `Enigo`, retrying with exponential backoff
+    /// until it succeeds or the app shuts down.
+    pub fn connect(&self, app_handle: &tauri::AppHandle) -> Option<Enigo> {
+        let mut delay = FIRST_RETRY_DELAY;
+        let mut attempts = 0;
+        loop {
+            match Enigo::new(&Settings::default()) {
+                #[allow(unused_mut)]
+                Ok(mut enigo) => {
+                    #[cfg(target_os = "linux")]
+                    enigo.set_delay(0);
+
+                    if attempts > 0 {
+                        log::info!(
+                            target: target::CLICKER,
+                            "Input backend connected after {} retries",
+                            attempts
+                        );
+                    }
+                    self.set(app_handle, BackendStatus::Ready);
+                    return Some(enigo);
+                }
+                Err(e) => {
+                    attempts += 1;
+                    log::error!(
+                        target: target::CLICKER,
+                        "Failed to create input backend (attempt {}), retrying in {:?}: {}",
+                        attempts,
+                        delay,
+                        e
+                    );
+                    self.set(
+                        app_handle,
+                        BackendStatus::Failed {
+                            error: e.to_string(),
+                            attempts,
+                            retry_in_ms: delay.as_millis() as u64,
+                        },
+                    );
+                    if app_handle.state::<ClickerState>().wait_for_shutdown(delay) {
+                        return None;
+                    }
+                    delay = (delay * 2).min(MAX_RETRY_DELAY);
+                }
+            }
+        }
+    }
+
+    /// Drops a backend that stopped working and connects a new one.
+    pub fn reconnect(&self, app_handle: &tauri::AppHandle, error: String) -> Option<Enigo> {
+        log::error!(target: target::CLICKER, "Input backend stopped working: {}", error);
+        self.set(
+            app_handle,
+            BackendStatus::Failed {
+                error,
+                attempts: 0,
+                retry_in_ms: 0,
+            },
+        );
+        self.connect(app_handle)
+    }
+}
+
+#[tauri::command]
+pub fn get_backend_status(backend: tauri::State<InputBackend>) -> BackendStatus {
+    backend.status()
+}
@@ -0,0 +1,96 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use ts_rs::TS;
+
+use crate::audio::{Cue, SoundSettings};
+use crate::backend::BackendStatus;
+use crate::clicker::{Channel, ClickerErrorKind, ClickerErrorPayload, ClickerStatus};
+use crate::clicker_state::{Channels, ClickerPhase, StopReason};
+use crate::failsafe::FailsafeCorner;
+use crate::history::SessionRecord;
+use crate::hotkey_validation::{HotkeyIssue, HotkeyValidation, IssueKind, IssueSeverity};
+use crate::notifications::NotificationSettings;
+use crate::overlay::{OverlayPosition, OverlaySettings};
+use crate::profiles::Profile;
+use crate::settings::{AutoclickerSettings, ChannelTiming};
+use crate::stats::{ChannelStats, ClickStats};
+use crate::store_keys::{store, TempState};
+use crate::supervisor::{Worker, WorkerCrash};
+use crate::transfer::{ConflictStrategy, ImportSummary};
+
+/// Where the frontend imports its store types and defaults from, relative to
+/// this crate.
+const OUTPUT: &str = "../src/lib/bindings.ts";
+
+fn declaration<T: TS>() -> String {
+    format!("export {}\n", T::decl())
+}
+
+fn constant<T: Serialize>(name: &str, ty: &str, value: &T) -> String {
+    let value = serde_json::to_string_pretty(value).unwrap_or_else(|_| "{}".to_string());
+    format!("export const {}: {} = {}\n", name, ty, value)
+}
+
+/// Renders the TypeScript module describing both stores and the command
+/// results. Store names come from `store_keys`, key names and types from the
+/// serde layout of the Rust types, and defaults from their `Default` impls.
+pub fn render() -> String {
+    [
+        "// Generated from the Rust types by `cargo test export_bindings`. Do not edit.\n"
+            .to_string(),
+        declaration::<FailsafeCorner>(),
+        declaration::<ChannelTiming>(),
+        declaration::<OverlayPosition>(),
+        declaration::<OverlaySettings>(),
+        declaration::<Cue>(),
+        declaration::<SoundSettings>(),
+        declaration::<NotificationSettings>(),
+        declaration::<Profile>(),
+        declaration::<AutoclickerSettings>(),
+        declaration::<TempState>(),
+        declaration::<Channel>(),
+        declaration::<Channels>(),
+        declaration::<ClickerPhase>(),
+        declaration::<StopReason>(),
+        declaration::<ClickerStatus>(),
+        declaration::<ClickerErrorKind>(),
+        declaration::<ClickerErrorPayload>(),
+        declaration::<BackendStatus>(),
+        declaration::<ChannelStats>(),
+        declaration::<ClickStats>(),
+        declaration::<SessionRecord>(),
+        declaration::<Worker>(),
+        declaration::<WorkerCrash>(),
+        declaration::<IssueKind>(),
+        declaration::<IssueSeverity>(),
+        declaration::<HotkeyIssue>(),
+        declaration::<HotkeyValidation>(),
+        declaration::<ConflictStrategy>(),
+        declaration::<ImportSummary>(),
+        format!(
+            "export const AUTOCLICKER_STORE = {:?}\nexport const TEMP_STORE = {:?}\n",
+            store::AUTOCLICKER,
+            store::TEMP
+        ),
+        constant(
+            "AUTOCLICKER_DEFAULTS",
+            &AutoclickerSettings::name(),
+            &AutoclickerSettings::default(),
+        ),
+        constant("TEMP_DEFAULTS", &TempState::name(), &TempState::default()),
+    ]
+    .join("\n")
+}
+
+/// Rewrites the bindings when they're out of date, the way ts-rs exports its
+/// own. CI runs this and fails if the checked-in file changed.
+#[test]
+fn export_bindings() {
+    let output = Path::new(env!("CARGO_MANIFEST_DIR")).join(OUTPUT);
+    let contents = render();
+    if fs::read_to_string(&output).is_ok_and(|current| current == contents) {
+        return;
+    }
+    fs::write(&output, contents).expect("failed to write the TypeScript bindings");
+}
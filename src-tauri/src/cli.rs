@@ -0,0 +1,248 @@
+use std::fs::{self, File, TryLockError};
+use std::io;
+use std::path::PathBuf;
+use tauri::Manager;
+
+use crate::clicker_state::{ClickerState, StopReason};
+use crate::logging::target;
+use crate::profiles;
+use crate::transfer::{self, ConflictStrategy, TransferError};
+use crate::tray;
+
+const USAGE: &str = "usage:
+  autoclicker [--start | --stop | --toggle] [--profile NAME] [--show]
+  autoclicker export <file> [--profile NAME]
+  autoclicker import <file> [--on-conflict rename|overwrite|skip]
+
+If the app is already running, the options are passed on to that instance.
+export and import refuse to run then, since it would overwrite the stores; quit
+it first. Files ending in .toml are written as TOML, anything else as JSON.";
+
+const LOCK_FILE: &str = "instance.lock";
+
+/// A subcommand that runs against the stores and exits without opening the window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Export {
+        path: PathBuf,
+        profile: Option<String>,
+    },
+    Import {
+        path: PathBuf,
+        on_conflict: ConflictStrategy,
+    },
+}
+
+/// Parses the process arguments (without the program name). Returns `None` when
+/// no subcommand was given, so the app starts normally.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<CliCommand>, String> {
+    let mut args = args.into_iter();
+    let Some(subcommand) = args.next() else {
+        return Ok(None);
+    };
+    if subcommand != "export" && subcommand != "import" {
+        return Ok(None);
+    }
+
+    let mut path = None;
+    let mut profile = None;
+    let mut on_conflict = ConflictStrategy::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--profile" if subcommand == "export" => {
+                profile = Some(args.next().ok_or("--profile needs a profile name")?);
+            }
+            "--on-conflict" if subcommand == "import" => {
+                on_conflict = args
+                    .next()
+                    .ok_or("--on-conflict needs a strategy")?
+                    .parse()?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+    let path = path.ok_or_else(|| format!("{} needs a file path", subcommand))?;
+
+    Ok(Some(if subcommand == "export" {
+        CliCommand::Export { path, profile }
+    } else {
+        CliCommand::Import { path, on_conflict }
+    }))
+}
+
+/// Something to do to the running app, given as an option when launching it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Control {
+    Start,
+    Stop,
+    Toggle,
+    Profile(String),
+    Show,
+}
+
+/// Parses the control options of a normal launch (without the program name).
+/// Launchers add arguments of their own, like `-psn_…` or an unexpanded `%U`,
+/// so anything else is logged and ignored rather than refusing to start.
+pub fn parse_controls(args: impl IntoIterator<Item = String>) -> Vec<Control> {
+    let mut args = args.into_iter();
+    let mut controls = Vec::new();
+    while let Some(arg) = args.next() {
+        controls.push(match arg.as_str() {
+            "--start" => Control::Start,
+            "--stop" => Control::Stop,
+            "--toggle" => Control::Toggle,
+            "--profile" => match args.next() {
+                Some(name) => Control::Profile(name),
+                None => {
+                    log::warn!(target: target::APP, "Ignored --profile without a profile name");
+                    continue;
+                }
+            },
+            "--show" => Control::Show,
+            _ => {
+                log::warn!(target: target::APP, "Ignored unknown argument {}", arg);
+                continue;
+            }
+        });
+    }
+    controls
+}
+
+/// Applies `controls` in order. Failures are logged rather than returned, since
+/// there's nobody to show them to.
+pub fn apply_controls(app_handle: &tauri::AppHandle, controls: Vec<Control>) {
+    // Another launch can arrive while this one is still setting up.
+    let Some(state) = app_handle.try_state::<ClickerState>() else {
+        log::warn!(target: target::APP, "Ignored {:?} during startup", controls);
+        return;
+    };
+    for control in controls {
+        let result = match &control {
+            Control::Start => state.arm().map(|_| ()).map_err(|e| e.to_string()),
+            Control::Stop => {
+                state.stop(StopReason::User);
+                Ok(())
+            }
+            Control::Toggle if state.is_running() => {
+                state.stop(StopReason::User);
+                Ok(())
+            }
+            Control::Toggle => state.arm().map(|_| ()).map_err(|e| e.to_string()),
+            Control::Profile(name) => profiles::switch_profile(app_handle.clone(), name.clone())
+                .map_err(|e| e.to_string()),
+            Control::Show => {
+                tray::show_window(app_handle);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            log::error!(target: target::APP, "{:?} failed: {}", control, e);
+        }
+    }
+}
+
+/// Handles another launch of the app, which the single-instance plugin ends
+/// right away and hands its arguments (with the program name) to. A bare launch
+/// brings the window up; one with options only applies them. Subcommands never
+/// get here: they skip the plugin so they can report back to their terminal.
+pub fn forward(app_handle: &tauri::AppHandle, argv: Vec<String>, _cwd: String) {
+    log::info!(target: target::APP, "Another instance was launched with {:?}", argv);
+    // Another launch can arrive while this one is still setting up.
+    if app_handle.try_state::<ClickerState>().is_none() {
+        log::warn!(target: target::APP, "Ignored {:?} during startup", argv);
+        return;
+    }
+    let controls = parse_controls(argv.into_iter().skip(1));
+    if controls.is_empty() {
+        tray::show_window(app_handle);
+    } else {
+        apply_controls(app_handle, controls);
+    }
+}
+
+/// An exclusive lock on a file in the app data dir, held by whichever process
+/// owns the stores: the app while it runs, or a subcommand while it runs on its
+/// own. The OS releases it when the process exits, however it exits.
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Returns `None` when another process holds the lock.
+    pub fn acquire(app_handle: &tauri::AppHandle) -> io::Result<Option<Self>> {
+        let dir = app_handle.path().app_data_dir().map_err(io::Error::other)?;
+        fs::create_dir_all(&dir)?;
+        let file = File::create(dir.join(LOCK_FILE))?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+}
+
+pub fn print_usage(error: &str) {
+    eprintln!("{}\n\n{}", error, USAGE);
+}
+
+/// Runs `command` and describes what it did.
+fn run_command(
+    app_handle: &tauri::AppHandle,
+    command: CliCommand,
+) -> Result<String, TransferError> {
+    match command {
+        CliCommand::Export { path, profile } => {
+            transfer::export_to(app_handle, &path, profile.as_deref())
+                .map(|_| format!("Exported settings to {}", path.display()))
+        }
+        CliCommand::Import { path, on_conflict } => {
+            transfer::import_from(app_handle, &path, on_conflict).map(|summary| {
+                let mut lines = vec![format!("Imported settings from {}", path.display())];
+                if summary.settings_replaced {
+                    lines.push("  global settings replaced".to_string());
+                }
+                for name in &summary.added {
+                    lines.push(format!("  added profile \"{}\"", name));
+                }
+                for name in &summary.overwritten {
+                    lines.push(format!("  overwrote profile \"{}\"", name));
+                }
+                for (name, stored_as) in &summary.renamed {
+                    lines.push(format!("  imported \"{}\" as \"{}\"", name, stored_as));
+                }
+                for name in &summary.skipped {
+                    lines.push(format!("  skipped existing profile \"{}\"", name));
+                }
+                lines.join("\n")
+            })
+        }
+    }
+}
+
+/// Runs `command` and returns the process exit code. Refuses to while the app is
+/// running, since it would overwrite the stores with its own copy.
+pub fn execute(app_handle: &tauri::AppHandle, command: CliCommand) -> i32 {
+    let _lock = match InstanceLock::acquire(app_handle) {
+        Ok(Some(lock)) => lock,
+        Ok(None) => {
+            eprintln!("error: autoclicker is already running; quit it and try again");
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("error: could not check for a running autoclicker: {}", e);
+            return 1;
+        }
+    };
+    match run_command(app_handle, command) {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
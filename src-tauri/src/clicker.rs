@@ -0,0 +1,279 @@
+use enigo::{Button, Direction::Release, Enigo, Mouse, Settings};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use ts_rs::TS;
+
+use crate::clicker_state::{Channels, ClickerPhase, ClickerState, StopReason};
+use crate::logging::target;
+use crate::settings::{self, AutoclickerSettings, ChannelTiming, SharedSettings};
+
+/// A mouse button the clicker can drive, toggled by its own hotkey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum Channel {
+    Left,
+    Right,
+}
+
+impl Channel {
+    pub const ALL: [Channel; 2] = [Channel::Left, Channel::Right];
+
+    pub fn event(self) -> &'static str {
+        match self {
+            Channel::Left => "left-hotkey-activated",
+            Channel::Right => "right-hotkey-activated",
+        }
+    }
+
+    pub fn button(self) -> Button {
+        match self {
+            Channel::Left => Button::Left,
+            Channel::Right => Button::Right,
+        }
+    }
+}
+
+impl AutoclickerSettings {
+    pub fn channel(&self, channel: Channel) -> &ChannelTiming {
+        match channel {
+            Channel::Left => &self.left_channel,
+            Channel::Right => &self.right_channel,
+        }
+    }
+
+    pub fn channel_mut(&mut self, channel: Channel) -> &mut ChannelTiming {
+        match channel {
+            Channel::Left => &mut self.left_channel,
+            Channel::Right => &mut self.right_channel,
+        }
+    }
+}
+
+/// When each active channel clicks next. Channels keep their own pace; one that
+/// fell behind is rescheduled from now instead of bursting to catch up.
+#[derive(Default)]
+pub struct ClickSchedule {
+    next: HashMap<Channel, Instant>,
+}
+
+impl ClickSchedule {
+    /// The channels due at `now`. A channel that just became active is due
+    /// immediately.
+    pub fn due(&mut self, channels: Channels, now: Instant) -> Vec<Channel> {
+        self.next.retain(|channel, _| channels.get(*channel));
+        channels
+            .active()
+            .filter(|channel| *self.next.entry(*channel).or_insert(now) <= now)
+            .collect()
+    }
+
+    /// Schedules the channel's next click. Returns whether this one ran so late
+    /// that the next was already due, i.e. a deadline was missed.
+    pub fn clicked(&mut self, channel: Channel, now: Instant, interval: Duration) -> bool {
+        let due = self.next.entry(channel).or_insert(now);
+        let missed = *due + interval < now;
+        *due = if missed { now } else { *due } + interval;
+        missed
+    }
+
+    pub fn next_wake(&self) -> Option<Instant> {
+        self.next.values().min().copied()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickerStatus {
+    pub phase: ClickerPhase,
+    pub is_running: bool,
+    pub left_active: bool,
+    pub right_active: bool,
+    pub hold_mode: bool,
+    pub left_channel: ChannelTiming,
+    pub right_channel: ChannelTiming,
+    pub active_profile: String,
+}
+
+#[derive(Debug)]
+pub enum ClickerError {
+    NotRunning,
+    /// No input backend could be created; holds the backend's error.
+    NoBackend(String),
+    InvalidSpeed(f64),
+    InvalidRandomization(f64),
+    Store(tauri_store::Error),
+}
+
+/// Which `ClickerError` happened, for scripts to branch on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum ClickerErrorKind {
+    NotRunning,
+    NoBackend,
+    InvalidSpeed,
+    InvalidRandomization,
+    Store,
+}
+
+/// What a `ClickerError` looks like to the frontend.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(rename = "ClickerError")]
+pub struct ClickerErrorPayload {
+    pub kind: ClickerErrorKind,
+    pub message: String,
+}
+
+impl ClickerError {
+    pub fn kind(&self) -> ClickerErrorKind {
+        match self {
+            ClickerError::NotRunning => ClickerErrorKind::NotRunning,
+            ClickerError::NoBackend(_) => ClickerErrorKind::NoBackend,
+            ClickerError::InvalidSpeed(_) => ClickerErrorKind::InvalidSpeed,
+            ClickerError::InvalidRandomization(_) => ClickerErrorKind::InvalidRandomization,
+            ClickerError::Store(_) => ClickerErrorKind::Store,
+        }
+    }
+}
+
+impl fmt::Display for ClickerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClickerError::NotRunning => write!(f, "the clicker is not running"),
+            ClickerError::NoBackend(e) => write!(f, "no working input backend: {}", e),
+            ClickerError::InvalidSpeed(speed) => write!(
+                f,
+                "click speed must be a positive number of milliseconds, got {}",
+                speed
+            ),
+            ClickerError::InvalidRandomization(randomization) => write!(
+                f,
+                "randomization must be between 0 and 100 percent, got {}",
+                randomization
+            ),
+            ClickerError::Store(e) => write!(f, "failed to update the store: {}", e),
+        }
+    }
+}
+
+impl From<tauri_store::Error> for ClickerError {
+    fn from(e: tauri_store::Error) -> Self {
+        ClickerError::Store(e)
+    }
+}
+
+/// Serialized as `{ kind, message }` so scripts can branch on the kind.
+impl Serialize for ClickerError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ClickerErrorPayload {
+            kind: self.kind(),
+            message: self.to_string(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Releases the buttons of `channels`, which the clicker may have left pressed,
+/// from a throwaway `Enigo` so it works regardless of what the click thread is
+/// doing. Other buttons are left alone; the user may be holding them.
+pub fn release_held_buttons(channels: Channels) {
+    if !channels.any() {
+        return;
+    }
+    match Enigo::new(&Settings::default()) {
+        Ok(mut enigo) => {
+            for channel in channels.active() {
+                if let Err(e) = enigo.button(channel.button(), Release) {
+                    log::error!(
+                        target: target::CLICKER,
+                        "Failed to release {:?} button: {}",
+                        channel.button(),
+                        e
+                    );
+                }
+            }
+        }
+        Err(e) => log::error!(
+            target: target::CLICKER,
+            "Failed to create input backend to release buttons: {}",
+            e
+        ),
+    }
+}
+
+pub fn status(app_handle: &tauri::AppHandle) -> ClickerStatus {
+    let settings = app_handle.state::<SharedSettings>().snapshot();
+    let phase = app_handle.state::<ClickerState>().phase();
+    ClickerStatus {
+        phase,
+        is_running: phase.is_running(),
+        left_active: phase.channels().left,
+        right_active: phase.channels().right,
+        hold_mode: settings.hold_mode,
+        left_channel: settings.left_channel,
+        right_channel: settings.right_channel,
+        active_profile: settings.active_profile,
+    }
+}
+
+/// Starts listening for the channel hotkeys. Starting twice is not an error.
+#[tauri::command]
+pub fn start(app_handle: tauri::AppHandle) -> Result<ClickerStatus, ClickerError> {
+    app_handle.state::<ClickerState>().arm()?;
+    Ok(status(&app_handle))
+}
+
+#[tauri::command]
+pub fn stop(app_handle: tauri::AppHandle) -> Result<ClickerStatus, ClickerError> {
+    app_handle.state::<ClickerState>().stop(StopReason::User);
+    Ok(status(&app_handle))
+}
+
+/// Flips a channel, or sets it when `active` is given.
+#[tauri::command]
+pub fn toggle_channel(
+    app_handle: tauri::AppHandle,
+    channel: Channel,
+    active: Option<bool>,
+) -> Result<ClickerStatus, ClickerError> {
+    app_handle
+        .state::<ClickerState>()
+        .set_channel(channel, active)?;
+    Ok(status(&app_handle))
+}
+
+/// Sets one channel's timing, or both when `channel` is omitted. Randomization
+/// is left as it is unless given.
+#[tauri::command]
+pub fn set_speed(
+    app_handle: tauri::AppHandle,
+    channel: Option<Channel>,
+    click_speed: f64,
+    randomization: Option<f64>,
+) -> Result<ClickerStatus, ClickerError> {
+    if !click_speed.is_finite() || click_speed <= 0.0 {
+        return Err(ClickerError::InvalidSpeed(click_speed));
+    }
+    if let Some(randomization) = randomization {
+        if !(0.0..=100.0).contains(&randomization) {
+            return Err(ClickerError::InvalidRandomization(randomization));
+        }
+    }
+    let channels = channel.map_or(Channel::ALL.to_vec(), |channel| vec![channel]);
+    settings::update(&app_handle, |settings| {
+        for channel in channels {
+            let timing = settings.channel_mut(channel);
+            timing.click_speed = click_speed;
+            timing.randomization = randomization.unwrap_or(timing.randomization);
+        }
+        Ok::<_, ClickerError>(())
+    })?;
+    Ok(status(&app_handle))
+}
+
+#[tauri::command]
+pub fn get_status(app_handle: tauri::AppHandle) -> ClickerStatus {
+    status(&app_handle)
+}
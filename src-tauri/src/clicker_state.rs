@@ -0,0 +1,488 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tauri_store::{ManagerExt, StoreState};
+use ts_rs::TS;
+
+use crate::audio::{AudioCues, Cue};
+use crate::backend::InputBackend;
+use crate::clicker::{self, Channel, ClickerError};
+use crate::history::SessionHistory;
+use crate::logging::target;
+use crate::notifications;
+use crate::overlay;
+use crate::settings::SharedSettings;
+use crate::stats::StatsTracker;
+use crate::store_keys::{store, temp_keys, TempState};
+use crate::supervisor::MutexExt;
+use crate::tray;
+
+/// Which channels are clicking.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct Channels {
+    pub left: bool,
+    pub right: bool,
+}
+
+impl Channels {
+    pub fn get(self, channel: Channel) -> bool {
+        match channel {
+            Channel::Left => self.left,
+            Channel::Right => self.right,
+        }
+    }
+
+    pub fn with(mut self, channel: Channel, active: bool) -> Self {
+        match channel {
+            Channel::Left => self.left = active,
+            Channel::Right => self.right = active,
+        }
+        self
+    }
+
+    pub fn any(self) -> bool {
+        self.left || self.right
+    }
+
+    pub fn active(self) -> impl Iterator<Item = Channel> {
+        Channel::ALL.into_iter().filter(move |c| self.get(*c))
+    }
+}
+
+/// `Idle` -> `Armed` (listening for hotkeys) -> `Clicking` (at least one channel
+/// on) and back, with every stop passing through `Stopping`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum ClickerPhase {
+    #[default]
+    Idle,
+    Armed,
+    Clicking(Channels),
+    Stopping,
+}
+
+impl ClickerPhase {
+    pub fn is_running(self) -> bool {
+        matches!(self, ClickerPhase::Armed | ClickerPhase::Clicking(_))
+    }
+
+    pub fn channels(self) -> Channels {
+        match self {
+            ClickerPhase::Clicking(channels) => channels,
+            _ => Channels::default(),
+        }
+    }
+
+    fn with_channels(channels: Channels) -> Self {
+        if channels.any() {
+            ClickerPhase::Clicking(channels)
+        } else {
+            ClickerPhase::Armed
+        }
+    }
+
+    fn temp_state(self) -> TempState {
+        let channels = self.channels();
+        TempState {
+            is_running: self.is_running(),
+            hotkey_left_active: channels.left,
+            hotkey_right_active: channels.right,
+        }
+    }
+}
+
+/// Every stop passes through `Stopping` on its way to `Idle`.
+const STOP_STEPS: [ClickerPhase; 2] = [ClickerPhase::Stopping, ClickerPhase::Idle];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum StopReason {
+    /// The stop button or the `stop` command.
+    User,
+    EmergencyStop,
+    Failsafe,
+    /// The cursor moved; only the channels stop.
+    UserTookOver,
+    /// The input backend failed; see the `backend-error` event.
+    BackendError,
+    /// A background thread panicked; see the `worker-crashed` event.
+    WorkerCrashed,
+    /// The app is quitting.
+    AppExit,
+}
+
+impl StopReason {
+    fn event(self) -> Option<&'static str> {
+        match self {
+            StopReason::User => None,
+            StopReason::EmergencyStop => Some("emergency-stop"),
+            StopReason::Failsafe => Some("failsafe-triggered"),
+            StopReason::UserTookOver => Some("user-took-over"),
+            StopReason::BackendError | StopReason::WorkerCrashed | StopReason::AppExit => None,
+        }
+    }
+}
+
+/// Whether the `temp` store left by the last run asks for the clicker to be
+/// armed again. `isRunning` can't tell: a crash leaves it set, a clean quit
+/// clears it.
+fn resumes(temp: &StoreState, resume_previous_session: bool) -> bool {
+    resume_previous_session && temp.get_or(temp_keys::RUNNING_AT_EXIT, false)
+}
+
+/// The one owner of the clicker's runtime state. Every change goes through a
+/// transition here, which then writes the `temp` store and emits events; nothing
+/// reads the state back from the store.
+///
+/// Outputs are published with `phase` unlocked. Some of them run on the main
+/// thread right away when called from it (tray, overlay), and those read the
+/// phase again.
+pub struct ClickerState {
+    app_handle: tauri::AppHandle,
+    /// Held for a whole transition, publishing included, so outputs go out in
+    /// the same order as the phase changes. Only ever taken before `phase`.
+    transitions: Mutex<()>,
+    phase: Mutex<ClickerPhase>,
+    /// Signalled on every transition, so the click thread can sleep until it
+    /// has something to do.
+    changed: Condvar,
+    /// Set once on exit; the worker threads return when they see it.
+    shutting_down: AtomicBool,
+}
+
+impl ClickerState {
+    /// Starts idle and resets the `temp` store, so a crash mid-click can't come
+    /// back up clicking. With `resume_previous_session` on, a clicker that was
+    /// running when the app last quit is armed again, still with every channel
+    /// off.
+    pub fn new(app_handle: tauri::AppHandle) -> Self {
+        let temp = app_handle
+            .store_collection()
+            .raw_state(store::TEMP)
+            .unwrap_or_default();
+        let resume = resumes(
+            &temp,
+            app_handle
+                .state::<SharedSettings>()
+                .snapshot()
+                .resume_previous_session,
+        );
+        let state = Self {
+            app_handle,
+            transitions: Mutex::new(()),
+            phase: Mutex::new(ClickerPhase::Idle),
+            changed: Condvar::new(),
+            shutting_down: AtomicBool::new(false),
+        };
+        state.write_store(ClickerPhase::Idle);
+        // Only a clean quit sets it again; a crash in this session must not resume.
+        state.write_running_at_exit(false);
+        if resume {
+            log::info!(target: target::CLICKER, "Resuming the previous session");
+            if let Err(e) = state.arm() {
+                log::warn!(target: target::CLICKER, "Not resuming the clicker: {}", e);
+            }
+        }
+        state
+    }
+
+    pub fn phase(&self) -> ClickerPhase {
+        *self.phase.lock_unpoisoned()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.phase().is_running()
+    }
+
+    pub fn is_active(&self, channel: Channel) -> bool {
+        self.phase().channels().get(channel)
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Blocks until at least one channel is clicking and returns them, or
+    /// `None` once the app is shutting down.
+    pub fn wait_for_clicking(&self) -> Option<Channels> {
+        let phase = self
+            .changed
+            .wait_while(self.phase.lock_unpoisoned(), |phase| {
+                !self.is_shutting_down() && !matches!(phase, ClickerPhase::Clicking(_))
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        (!self.is_shutting_down()).then(|| phase.channels())
+    }
+
+    /// Sleeps for `timeout`, returning early if `channels` stop being the ones
+    /// clicking, so a channel switched on meanwhile doesn't wait for the other.
+    pub fn wait_while_clicking(&self, channels: Channels, timeout: Duration) {
+        let _ = self
+            .changed
+            .wait_timeout_while(self.phase.lock_unpoisoned(), timeout, |phase| {
+                !self.is_shutting_down() && *phase == ClickerPhase::Clicking(channels)
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+
+    /// Sleeps for `timeout` or until shutdown starts. Returns whether it has.
+    pub fn wait_for_shutdown(&self, timeout: Duration) -> bool {
+        let _ = self
+            .changed
+            .wait_timeout_while(self.phase.lock_unpoisoned(), timeout, |_| {
+                !self.is_shutting_down()
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        self.is_shutting_down()
+    }
+
+    /// Starts listening for the channel hotkeys. Arming twice is a no-op; arming
+    /// without a working input backend is refused.
+    pub fn arm(&self) -> Result<ClickerPhase, ClickerError> {
+        self.app_handle.state::<InputBackend>().ensure_usable()?;
+        let (_, phase) = self.transition(|phase| {
+            Ok(match phase {
+                ClickerPhase::Idle => vec![ClickerPhase::Armed],
+                _ => Vec::new(),
+            })
+        })?;
+        Ok(phase)
+    }
+
+    /// Sets a channel, or flips it when `active` is `None`. Only allowed while
+    /// running.
+    pub fn set_channel(
+        &self,
+        channel: Channel,
+        active: Option<bool>,
+    ) -> Result<ClickerPhase, ClickerError> {
+        let mut cue = None;
+        let (_, phase) = self.transition(|phase| {
+            if !phase.is_running() {
+                return Err(ClickerError::NotRunning);
+            }
+            let channels = phase.channels();
+            let active = active.unwrap_or(!channels.get(channel));
+            let next = ClickerPhase::with_channels(channels.with(channel, active));
+            if next == phase {
+                return Ok(Vec::new());
+            }
+            cue = Some(if active {
+                Cue::ChannelOn
+            } else {
+                Cue::ChannelOff
+            });
+            Ok(vec![next])
+        })?;
+        if let Some(cue) = cue {
+            self.app_handle
+                .state::<AudioCues>()
+                .play(&self.app_handle, cue);
+        }
+        Ok(phase)
+    }
+
+    /// Turns every channel off but keeps listening for hotkeys.
+    pub fn stop_channels(&self, reason: StopReason) {
+        let stopped = self.transition(|phase| {
+            Ok(match phase {
+                ClickerPhase::Clicking(_) => vec![ClickerPhase::Armed],
+                _ => Vec::new(),
+            })
+        });
+        if let Ok((previous @ ClickerPhase::Clicking(_), _)) = stopped {
+            self.emit_reason(reason, previous);
+        }
+    }
+
+    /// Stops everything regardless of the current phase, closing the session
+    /// in the history if one was running.
+    pub fn stop(&self, reason: StopReason) {
+        if let Ok((previous, _)) = self.transition(|_| Ok(STOP_STEPS.to_vec())) {
+            self.finish_stop(reason, previous);
+        }
+    }
+
+    /// Stops the clicker for good and wakes every thread waiting on it, so the
+    /// workers can see the flag and return. Only the first call does anything.
+    pub fn shut_down(&self) {
+        let mut first = false;
+        let stopped = self.transition(|_| {
+            // Set with the phase locked, so no waiter can miss the wakeup.
+            first = !self.shutting_down.swap(true, Ordering::SeqCst);
+            Ok(if first {
+                STOP_STEPS.to_vec()
+            } else {
+                Vec::new()
+            })
+        });
+        if let (true, Ok((previous, _))) = (first, stopped) {
+            self.write_running_at_exit(previous.is_running());
+            self.finish_stop(StopReason::AppExit, previous);
+        }
+    }
+
+    /// The rest of a stop from `previous`, once the phase is unlocked: lets go
+    /// of the buttons that were clicking, closes the session and reports it.
+    fn finish_stop(&self, reason: StopReason, previous: ClickerPhase) {
+        clicker::release_held_buttons(previous.channels());
+        if previous.is_running() {
+            let stats = self.app_handle.state::<StatsTracker>().snapshot();
+            self.app_handle
+                .state::<SessionHistory>()
+                .finish(reason, &stats);
+        }
+        self.emit_reason(reason, previous);
+    }
+
+    /// Moves the phase through the steps `plan` returns for the current one and
+    /// then publishes each step, with the phase unlocked but no other transition
+    /// able to start. Returns the phase before and after.
+    fn transition(
+        &self,
+        plan: impl FnOnce(ClickerPhase) -> Result<Vec<ClickerPhase>, ClickerError>,
+    ) -> Result<(ClickerPhase, ClickerPhase), ClickerError> {
+        let _transition = self.transitions.lock_unpoisoned();
+        let (previous, steps) = {
+            let mut phase = self.phase.lock_unpoisoned();
+            let previous = *phase;
+            let steps = plan(previous)?;
+            if let Some(last) = steps.last() {
+                *phase = *last;
+                self.changed.notify_all();
+            }
+            (previous, steps)
+        };
+        let mut current = previous;
+        for next in steps {
+            self.publish(current, next);
+            current = next;
+        }
+        Ok((previous, current))
+    }
+
+    /// Publishes the move from `previous` to `next`. Must not be called with the
+    /// phase locked.
+    fn publish(&self, previous: ClickerPhase, next: ClickerPhase) {
+        log::debug!(target: target::CLICKER, "{:?} -> {:?}", previous, next);
+        let stats = self.app_handle.state::<StatsTracker>();
+        if previous == ClickerPhase::Idle && next.is_running() {
+            stats.start_session();
+            let profile = self
+                .app_handle
+                .state::<SharedSettings>()
+                .snapshot()
+                .active_profile;
+            self.app_handle.state::<SessionHistory>().begin(profile);
+        }
+        let (before, after) = (previous.channels(), next.channels());
+        for channel in Channel::ALL {
+            if before.get(channel) != after.get(channel) {
+                if !after.get(channel) {
+                    stats.channel_stopped(channel);
+                }
+                self.emit(channel.event(), after.get(channel));
+            }
+        }
+        if before != after {
+            stats.emit(&self.app_handle);
+        }
+        if previous.temp_state() != next.temp_state() {
+            self.write_store(next);
+        }
+        self.emit("clicker-state", next);
+        tray::schedule_refresh(&self.app_handle);
+        if previous.is_running() != next.is_running() {
+            overlay::schedule_update(&self.app_handle);
+        }
+    }
+
+    fn write_store(&self, phase: ClickerPhase) {
+        let state: StoreState = match serde_json::to_value(phase.temp_state()) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().collect(),
+            _ => return,
+        };
+        if let Err(e) = self.app_handle.store_collection().patch(store::TEMP, state) {
+            log::error!(target: target::STORE, "Failed to write clicker state to store: {}", e);
+        }
+    }
+
+    /// Saved right away, since it decides what the next launch does.
+    fn write_running_at_exit(&self, running: bool) {
+        let collection = self.app_handle.store_collection();
+        let result = collection
+            .set(store::TEMP, temp_keys::RUNNING_AT_EXIT, running)
+            .and_then(|_| collection.save_now(store::TEMP));
+        if let Err(e) = result {
+            log::error!(target: target::STORE, "Failed to record the clicker state at exit: {}", e);
+        }
+    }
+
+    fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        self.app_handle
+            .emit(event, payload)
+            .unwrap_or_else(|e| log::warn!(target: target::APP, "Failed to emit {}: {}", event, e));
+    }
+
+    /// Reports a stop from `previous`, the phase the clicker was in before it.
+    fn emit_reason(&self, reason: StopReason, previous: ClickerPhase) {
+        log::info!(target: target::CLICKER, "Stopped: {:?}", reason);
+        if let Some(event) = reason.event() {
+            self.emit(event, ());
+        }
+        if let Some(cue) = Cue::for_stop(reason, previous) {
+            self.app_handle
+                .state::<AudioCues>()
+                .play(&self.app_handle, cue);
+        }
+        // Stopping an idle clicker, e.g. with the panic hotkey, isn't news.
+        if previous.is_running() {
+            notifications::notify_stop(&self.app_handle, reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `temp` store as the last run left it: `phase` as written by its final
+    /// transition, plus the exit flag if it got to record one.
+    fn temp_after(phase: ClickerPhase, running_at_exit: Option<bool>) -> StoreState {
+        let mut temp: StoreState = match serde_json::to_value(phase.temp_state()) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().collect(),
+            _ => unreachable!(),
+        };
+        if let Some(running) = running_at_exit {
+            temp.set(temp_keys::RUNNING_AT_EXIT, running);
+        }
+        temp
+    }
+
+    #[test]
+    fn resumes_after_a_clean_quit_while_running() {
+        let temp = temp_after(ClickerPhase::Idle, Some(true));
+        assert!(resumes(&temp, true));
+        assert!(!resumes(&temp, false));
+    }
+
+    #[test]
+    fn does_not_resume_after_a_clean_quit_while_idle() {
+        assert!(!resumes(&temp_after(ClickerPhase::Idle, Some(false)), true));
+    }
+
+    #[test]
+    fn does_not_resume_after_a_crash() {
+        let clicking = ClickerPhase::Clicking(Channels {
+            left: true,
+            right: false,
+        });
+        // Launch cleared the flag, and the crash skipped `shut_down`.
+        assert!(!resumes(&temp_after(clicking, Some(false)), true));
+        // Stores written before the flag existed.
+        assert!(!resumes(&temp_after(clicking, None), true));
+    }
+}
@@ -0,0 +1,100 @@
+use enigo::{Enigo, Settings};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::Manager;
+
+use crate::backend::{BackendStatus, InputBackend};
+use crate::clicker::{self, ClickerStatus};
+use crate::logging::{self, LogError};
+use crate::settings::{AutoclickerSettings, SharedSettings};
+use crate::stats::{ClickStats, StatsTracker};
+
+/// Log lines included in a bundle.
+const BUNDLE_LOG_LINES: usize = 500;
+
+/// Everything needed to make sense of a "clicking doesn't work" report.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostics {
+    pub app_version: String,
+    pub tauri_version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub display_server: String,
+    pub input_backend: &'static str,
+    /// Why the input backend couldn't be created, if it couldn't.
+    pub input_backend_error: Option<String>,
+    /// What the click thread's own backend is doing.
+    pub backend_status: BackendStatus,
+    pub status: ClickerStatus,
+    pub settings: AutoclickerSettings,
+    pub stats: ClickStats,
+    pub log_file: Option<PathBuf>,
+    pub recent_logs: Vec<String>,
+}
+
+#[cfg(target_os = "linux")]
+fn display_server() -> String {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    match (set("WAYLAND_DISPLAY"), set("DISPLAY")) {
+        (true, true) => "wayland (with XWayland)".to_string(),
+        (true, false) => "wayland".to_string(),
+        (false, true) => "x11".to_string(),
+        (false, false) => std::env::var("XDG_SESSION_TYPE").unwrap_or_else(|_| "none".to_string()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn display_server() -> String {
+    std::env::consts::OS.to_string()
+}
+
+/// The enigo backend compiled in for this platform.
+fn input_backend() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "SendInput"
+    } else if cfg!(target_os = "macos") {
+        "CGEvent"
+    } else {
+        "x11rb"
+    }
+}
+
+pub fn collect(app_handle: &tauri::AppHandle) -> Diagnostics {
+    let recent_logs = logging::recent_lines(app_handle, BUNDLE_LOG_LINES)
+        .unwrap_or_else(|e| vec![format!("Failed to read the log file: {}", e)]);
+    Diagnostics {
+        app_version: app_handle.package_info().version.to_string(),
+        tauri_version: tauri::VERSION,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        display_server: display_server(),
+        input_backend: input_backend(),
+        input_backend_error: Enigo::new(&Settings::default())
+            .err()
+            .map(|e| e.to_string()),
+        backend_status: app_handle.state::<InputBackend>().status(),
+        status: clicker::status(app_handle),
+        settings: app_handle.state::<SharedSettings>().snapshot(),
+        stats: app_handle.state::<StatsTracker>().snapshot(),
+        log_file: logging::log_file(app_handle).ok(),
+        recent_logs,
+    }
+}
+
+/// Collects a diagnostics bundle and, when `path` is given, also writes it
+/// there as JSON for attaching to a bug report.
+#[tauri::command]
+pub fn collect_diagnostics(
+    app_handle: tauri::AppHandle,
+    path: Option<PathBuf>,
+) -> Result<Diagnostics, LogError> {
+    let diagnostics = collect(&app_handle);
+    if let Some(path) = path {
+        let contents = serde_json::to_string_pretty(&diagnostics).map_err(std::io::Error::from)?;
+        fs::write(&path, contents)?;
+        log::info!(target: logging::target::APP, "Wrote diagnostics to {}", path.display());
+    }
+    Ok(diagnostics)
+}
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::Monitor;
+use ts_rs::TS;
+
+use crate::logging::target;
+
+/// How close the cursor has to get to a corner, in the units `device_query`
+/// reports it in.
+const CORNER_MARGIN: i32 = 2;
+/// Monitor layout is queried through the event loop, so it is cached between polls.
+const MONITOR_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum FailsafeCorner {
+    #[default]
+    Off,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Any,
+}
+
+impl FailsafeCorner {
+    const CORNERS: [FailsafeCorner; 4] = [
+        FailsafeCorner::TopLeft,
+        FailsafeCorner::TopRight,
+        FailsafeCorner::BottomLeft,
+        FailsafeCorner::BottomRight,
+    ];
+
+    fn includes(self, corner: FailsafeCorner) -> bool {
+        self == corner || self == FailsafeCorner::Any
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ScreenRect {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+}
+
+impl ScreenRect {
+    /// The monitor's bounds in cursor coordinates. Monitors are measured in
+    /// physical pixels, which `device_query` uses too, except on macOS where it
+    /// reports logical points.
+    fn from_monitor(monitor: &Monitor) -> Self {
+        let scale = if cfg!(target_os = "macos") {
+            monitor.scale_factor()
+        } else {
+            1.0
+        };
+        let position = monitor.position();
+        let size = monitor.size();
+        Self::scaled((position.x, position.y), (size.width, size.height), scale)
+    }
+
+    fn scaled((x, y): (i32, i32), (width, height): (u32, u32), scale: f64) -> Self {
+        let left = (x as f64 / scale).round() as i32;
+        let top = (y as f64 / scale).round() as i32;
+        ScreenRect {
+            left,
+            top,
+            right: left + (width as f64 / scale).round() as i32 - 1,
+            bottom: top + (height as f64 / scale).round() as i32 - 1,
+        }
+    }
+
+    fn contains(&self, (x, y): (i32, i32)) -> bool {
+        (self.left..=self.right).contains(&x) && (self.top..=self.bottom).contains(&y)
+    }
+
+    /// The pixel in `corner`, and the direction pointing off the screen there.
+    fn corner(&self, corner: FailsafeCorner) -> ((i32, i32), (i32, i32)) {
+        match corner {
+            FailsafeCorner::TopLeft => ((self.left, self.top), (-1, -1)),
+            FailsafeCorner::TopRight => ((self.right, self.top), (1, -1)),
+            FailsafeCorner::BottomLeft => ((self.left, self.bottom), (-1, 1)),
+            _ => ((self.right, self.bottom), (1, 1)),
+        }
+    }
+}
+
+/// Whether the cursor is at one of the desktop's outer corners. A monitor's
+/// corner that borders another monitor, like those along the seam between two
+/// side by side, doesn't count: the cursor moves on past it.
+fn corner_hit(screens: &[ScreenRect], corner: FailsafeCorner, (x, y): (i32, i32)) -> bool {
+    let on_any_screen = |point| screens.iter().any(|screen| screen.contains(point));
+    screens.iter().any(|screen| {
+        FailsafeCorner::CORNERS
+            .into_iter()
+            .filter(|candidate| corner.includes(*candidate))
+            .any(|candidate| {
+                let ((corner_x, corner_y), (dx, dy)) = screen.corner(candidate);
+                (x - corner_x).abs() <= CORNER_MARGIN
+                    && (y - corner_y).abs() <= CORNER_MARGIN
+                    && !on_any_screen((corner_x + dx, corner_y))
+                    && !on_any_screen((corner_x, corner_y + dy))
+            })
+    })
+}
+
+/// Stops automation when the physical cursor reaches a screen corner, like
+/// PyAutoGUI's failsafe. With several monitors only the outer corners of the
+/// whole desktop count.
+#[derive(Default)]
+pub struct Failsafe {
+    screens: Vec<ScreenRect>,
+    last_refresh: Option<Instant>,
+}
+
+impl Failsafe {
+    fn refresh_screens(&mut self, app_handle: &tauri::AppHandle) {
+        match app_handle.available_monitors() {
+            Ok(monitors) => {
+                self.screens = monitors.iter().map(ScreenRect::from_monitor).collect();
+            }
+            Err(e) => {
+                log::warn!(target: target::HOTKEYS, "Failed to query monitors for failsafe: {}", e)
+            }
+        }
+        self.last_refresh = Some(Instant::now());
+    }
+
+    pub fn is_triggered(
+        &mut self,
+        app_handle: &tauri::AppHandle,
+        corner: FailsafeCorner,
+        coords: (i32, i32),
+    ) -> bool {
+        if corner == FailsafeCorner::Off {
+            return false;
+        }
+        let stale = self
+            .last_refresh
+            .is_none_or(|at| at.elapsed() >= MONITOR_REFRESH_INTERVAL);
+        if stale {
+            self.refresh_screens(app_handle);
+        }
+        corner_hit(&self.screens, corner, coords)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_HD: (u32, u32) = (1920, 1080);
+
+    #[test]
+    fn hits_the_corners_of_a_single_monitor() {
+        let screens = [ScreenRect::scaled((0, 0), FULL_HD, 1.0)];
+        assert!(corner_hit(&screens, FailsafeCorner::TopLeft, (0, 0)));
+        assert!(corner_hit(&screens, FailsafeCorner::TopLeft, (2, 1)));
+        assert!(corner_hit(
+            &screens,
+            FailsafeCorner::BottomRight,
+            (1919, 1079)
+        ));
+        assert!(!corner_hit(&screens, FailsafeCorner::TopRight, (0, 0)));
+        assert!(!corner_hit(&screens, FailsafeCorner::TopLeft, (3, 0)));
+        assert!(!corner_hit(&screens, FailsafeCorner::Off, (0, 0)));
+        for corner in [(0, 0), (1919, 0), (0, 1079), (1919, 1079)] {
+            assert!(corner_hit(&screens, FailsafeCorner::Any, corner));
+        }
+        assert!(!corner_hit(&screens, FailsafeCorner::Any, (960, 0)));
+    }
+
+    #[test]
+    fn ignores_the_seam_between_monitors_side_by_side() {
+        let screens = [
+            ScreenRect::scaled((0, 0), FULL_HD, 1.0),
+            ScreenRect::scaled((1920, 0), FULL_HD, 1.0),
+        ];
+        assert!(corner_hit(&screens, FailsafeCorner::TopLeft, (0, 0)));
+        assert!(corner_hit(&screens, FailsafeCorner::TopRight, (3839, 0)));
+        assert!(corner_hit(
+            &screens,
+            FailsafeCorner::BottomRight,
+            (3839, 1079)
+        ));
+        for seam in [(1919, 0), (1920, 0), (1919, 1079), (1920, 1079)] {
+            assert!(!corner_hit(&screens, FailsafeCorner::Any, seam));
+        }
+    }
+
+    #[test]
+    fn measures_scaled_monitors_in_logical_points() {
+        // A 2x laptop panel with a 1x monitor to its right, lined up at the top.
+        let screens = [
+            ScreenRect::scaled((0, 0), (2560, 1600), 2.0),
+            ScreenRect::scaled((1280, 0), FULL_HD, 1.0),
+        ];
+        assert!(corner_hit(&screens, FailsafeCorner::BottomLeft, (0, 799)));
+        assert!(corner_hit(&screens, FailsafeCorner::TopRight, (3199, 0)));
+        // The panel's right corners border the external monitor.
+        assert!(!corner_hit(&screens, FailsafeCorner::TopRight, (1279, 0)));
+        assert!(!corner_hit(
+            &screens,
+            FailsafeCorner::BottomRight,
+            (1279, 799)
+        ));
+        // Below the panel the external monitor's bottom left is a real corner.
+        assert!(corner_hit(
+            &screens,
+            FailsafeCorner::BottomLeft,
+            (1280, 1079)
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+use ts_rs::TS;
+
+use crate::clicker::Channel;
+use crate::clicker_state::StopReason;
+use crate::logging::target;
+use crate::stats::ClickStats;
+use crate::supervisor::MutexExt;
+
+const FILE_STEM: &str = "history";
+/// The current file is rotated once it grows past this.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+/// Rotated files kept next to the current one; the oldest is dropped.
+const ROTATED_FILES: usize = 2;
+
+/// One run of the clicker, from start to stop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRecord {
+    /// Milliseconds since the Unix epoch.
+    #[ts(type = "number")]
+    pub started_at: u64,
+    #[ts(type = "number")]
+    pub ended_at: u64,
+    pub profile: String,
+    /// The channels that clicked at least once.
+    pub channels: Vec<Channel>,
+    #[ts(type = "number")]
+    pub clicks: u64,
+    pub stop_reason: StopReason,
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    NoDataDir,
+    Io(std::io::Error),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::NoDataDir => write!(f, "the app data directory is unavailable"),
+            HistoryError::Io(e) => write!(f, "failed to access the session history: {}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for HistoryError {
+    fn from(e: std::io::Error) -> Self {
+        HistoryError::Io(e)
+    }
+}
+
+impl Serialize for HistoryError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+struct OpenSession {
+    started_at: u64,
+    profile: String,
+}
+
+/// Appends a JSON line per finished session to `history.jsonl` in the app data
+/// dir, rotating to `history.1.jsonl`, `history.2.jsonl` as it grows.
+pub struct SessionHistory {
+    dir: Option<PathBuf>,
+    /// Also serializes file access.
+    current: Mutex<Option<OpenSession>>,
+}
+
+impl SessionHistory {
+    pub fn new(app_handle: &tauri::AppHandle) -> Self {
+        let dir = app_handle
+            .path()
+            .app_data_dir()
+            .inspect_err(|e| log::warn!(target: target::APP, "Session history disabled: {}", e))
+            .ok();
+        Self {
+            dir,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Paths from newest to oldest.
+    fn paths(&self) -> Result<Vec<PathBuf>, HistoryError> {
+        let dir = self.dir.as_ref().ok_or(HistoryError::NoDataDir)?;
+        Ok((0..=ROTATED_FILES)
+            .map(|index| match index {
+                0 => dir.join(format!("{}.jsonl", FILE_STEM)),
+                index => dir.join(format!("{}.{}.jsonl", FILE_STEM, index)),
+            })
+            .collect())
+    }
+
+    pub fn begin(&self, profile: String) {
+        *self.current.lock_unpoisoned() = Some(OpenSession {
+            started_at: now_ms(),
+            profile,
+        });
+    }
+
+    /// Closes the open session, if any, and writes it out.
+    pub fn finish(&self, reason: StopReason, stats: &ClickStats) {
+        let mut current = self.current.lock_unpoisoned();
+        let Some(session) = current.take() else {
+            return;
+        };
+        let channels = Channel::ALL
+            .into_iter()
+            .filter(|channel| stats.channel(*channel).session_clicks > 0)
+            .collect();
+        let record = SessionRecord {
+            started_at: session.started_at,
+            ended_at: now_ms(),
+            profile: session.profile,
+            channels,
+            clicks: stats.session_clicks(),
+            stop_reason: reason,
+        };
+        if let Err(e) = self.append(&record) {
+            log::error!(target: target::APP, "Failed to record session: {}", e);
+        }
+    }
+
+    fn append(&self, record: &SessionRecord) -> Result<(), HistoryError> {
+        let paths = self.paths()?;
+        if let Some(parent) = paths[0].parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::metadata(&paths[0]).is_ok_and(|meta| meta.len() >= MAX_FILE_BYTES) {
+            for index in (1..paths.len()).rev() {
+                if paths[index - 1].exists() {
+                    fs::rename(&paths[index - 1], &paths[index])?;
+                }
+            }
+        }
+        let line = serde_json::to_string(record).map_err(std::io::Error::from)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&paths[0])?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Newest first. Lines that don't parse are skipped.
+    pub fn read(&self, limit: Option<usize>) -> Result<Vec<SessionRecord>, HistoryError> {
+        let _guard = self.current.lock_unpoisoned();
+        let mut records = Vec::new();
+        for path in self.paths()? {
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            records.extend(
+                contents
+                    .lines()
+                    .rev()
+                    .filter_map(|line| serde_json::from_str(line).ok()),
+            );
+            if limit.is_some_and(|limit| records.len() >= limit) {
+                break;
+            }
+        }
+        records.truncate(limit.unwrap_or(records.len()));
+        Ok(records)
+    }
+
+    pub fn clear(&self) -> Result<(), HistoryError> {
+        let _guard = self.current.lock_unpoisoned();
+        for path in self.paths()? {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Past sessions, newest first, optionally only the latest `limit`.
+#[tauri::command]
+pub fn get_history(
+    history: tauri::State<SessionHistory>,
+    limit: Option<usize>,
+) -> Result<Vec<SessionRecord>, HistoryError> {
+    history.read(limit)
+}
+
+#[tauri::command]
+pub fn clear_history(history: tauri::State<SessionHistory>) -> Result<(), HistoryError> {
+    history.clear()
+}
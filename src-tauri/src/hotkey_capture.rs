@@ -0,0 +1,180 @@
+use device_query::Keycode;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::hotkey_utils::{self, ParsedHotkey};
+use crate::supervisor::MutexExt;
+
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How much longer `capture_hotkey` waits for the hotkey loop to report a
+/// timeout before giving up on it.
+const CAPTURE_TIMEOUT_MARGIN: Duration = Duration::from_secs(1);
+/// How often the hotkey loop polls while a capture is pending.
+pub const CAPTURE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const CAPTURABLE_MOUSE_BUTTONS: [usize; 2] = [4, 5];
+
+/// Hand-off between `capture_hotkey` and the hotkey loop, which owns the
+/// `DeviceState` and advances a pending request on each of its polls.
+#[derive(Default)]
+pub struct HotkeyCapture {
+    pending: Mutex<Option<(u64, Sender<Option<String>>)>>,
+    next_id: AtomicU64,
+}
+
+impl HotkeyCapture {
+    /// Starts a new capture. A capture that is still pending is cancelled.
+    fn request(&self) -> (u64, Receiver<Option<String>>) {
+        let (sender, receiver) = mpsc::channel();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        *self.pending.lock_unpoisoned() = Some((id, sender));
+        (id, receiver)
+    }
+
+    /// The id of the pending request, which changes when a new one replaces it.
+    pub fn pending(&self) -> Option<u64> {
+        self.pending.lock_unpoisoned().as_ref().map(|(id, _)| *id)
+    }
+
+    /// Answers request `id`, unless a newer one has replaced it.
+    pub fn finish(&self, id: u64, result: Option<String>) {
+        let mut pending = self.pending.lock_unpoisoned();
+        if pending
+            .as_ref()
+            .is_some_and(|(pending_id, _)| *pending_id == id)
+        {
+            if let Some((_, sender)) = pending.take() {
+                let _ = sender.send(result);
+            }
+        }
+    }
+
+    /// Answers the pending request, if any, with no hotkey. For when the hotkey
+    /// loop that would have answered it stops.
+    pub fn cancel(&self) {
+        if let Some((_, sender)) = self.pending.lock_unpoisoned().take() {
+            let _ = sender.send(None);
+        }
+    }
+}
+
+/// One capture in progress. The hotkey loop feeds it every poll instead of
+/// blocking on it, so the panic hotkey and fail-safe keep working meanwhile.
+pub struct CaptureSession {
+    pub id: u64,
+    deadline: Instant,
+    previous_keys: Vec<Keycode>,
+    previous_mouse_buttons: Vec<bool>,
+}
+
+impl CaptureSession {
+    /// Keys held when the capture starts are ignored until pressed again.
+    pub fn new(id: u64, keys: &[Keycode], mouse_buttons: &[bool], now: Instant) -> Self {
+        Self {
+            id,
+            deadline: now + CAPTURE_TIMEOUT,
+            previous_keys: keys.to_vec(),
+            previous_mouse_buttons: mouse_buttons.to_vec(),
+        }
+    }
+
+    /// Looks at one poll's input. Returns `Some` once the capture is over, with
+    /// the canonical string of the new chord or mouse button, or `None` when a
+    /// bare `Escape` cancelled it or it timed out.
+    pub fn poll(
+        &mut self,
+        current_keys: &[Keycode],
+        current_mouse_buttons: &[bool],
+        now: Instant,
+    ) -> Option<Option<String>> {
+        if now >= self.deadline {
+            return Some(None);
+        }
+
+        for index in CAPTURABLE_MOUSE_BUTTONS {
+            let pressed = current_mouse_buttons.get(index).cloned().unwrap_or(false);
+            let was_pressed = self
+                .previous_mouse_buttons
+                .get(index)
+                .cloned()
+                .unwrap_or(false);
+            if pressed && !was_pressed {
+                return Some(Some(ParsedHotkey::MouseButton(index).to_string()));
+            }
+        }
+
+        let newly_pressed = current_keys.iter().any(|kc| {
+            !self.previous_keys.contains(kc)
+                && !hotkey_utils::is_modifier(*kc)
+                && hotkey_utils::keycode_name(*kc).is_some()
+        });
+        if newly_pressed {
+            let hotkey = ParsedHotkey::from_pressed_keys(current_keys);
+            let cancelled = matches!(
+                &hotkey,
+                ParsedHotkey::Chord { shift: false, ctrl: false, alt: false, keys }
+                    if keys.as_slice() == [Keycode::Escape]
+            );
+            return Some(if cancelled {
+                None
+            } else {
+                Some(hotkey.to_string())
+            });
+        }
+
+        self.previous_keys = current_keys.to_vec();
+        self.previous_mouse_buttons = current_mouse_buttons.to_vec();
+        None
+    }
+}
+
+#[tauri::command]
+pub async fn capture_hotkey(capture: State<'_, HotkeyCapture>) -> Result<Option<String>, String> {
+    let (id, receiver) = capture.request();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        receiver.recv_timeout(CAPTURE_TIMEOUT + CAPTURE_TIMEOUT_MARGIN)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    match result {
+        Ok(hotkey) => Ok(hotkey),
+        Err(RecvTimeoutError::Timeout) => {
+            // The hotkey loop missed it, so it's still pending.
+            capture.finish(id, None);
+            Ok(None)
+        }
+        Err(RecvTimeoutError::Disconnected) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_newer_request_replaces_the_pending_one() {
+        let capture = HotkeyCapture::default();
+        let (first, first_receiver) = capture.request();
+        let (second, second_receiver) = capture.request();
+        assert_eq!(capture.pending(), Some(second));
+
+        capture.finish(first, Some("F1".to_string()));
+        assert!(first_receiver.try_recv().is_err());
+        assert_eq!(capture.pending(), Some(second));
+
+        capture.finish(second, Some("F2".to_string()));
+        assert_eq!(second_receiver.try_recv(), Ok(Some("F2".to_string())));
+        assert_eq!(capture.pending(), None);
+    }
+
+    #[test]
+    fn cancel_answers_the_pending_request() {
+        let capture = HotkeyCapture::default();
+        let (_, receiver) = capture.request();
+        capture.cancel();
+        assert_eq!(receiver.try_recv(), Ok(None));
+        assert_eq!(capture.pending(), None);
+    }
+}
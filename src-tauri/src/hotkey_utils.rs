@@ -1,4 +1,13 @@
 use device_query::Keycode;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use crate::logging::target;
+use crate::supervisor::MutexExt;
+
+/// Unknown keys already warned about, so a bad hotkey polled every few
+/// milliseconds is logged once rather than flooding the log.
+static WARNED_UNKNOWN_KEYS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
 
 pub fn keycode_from_string(s: &str) -> Option<Keycode> {
     match s.to_uppercase().as_str() {
@@ -66,67 +75,359 @@ pub fn keycode_from_string(s: &str) -> Option<Keycode> {
         "TAB" => Some(Keycode::Tab),
         "BACKSPACE" => Some(Keycode::Backspace),
         "CAPSLOCK" => Some(Keycode::CapsLock),
+        "ESCAPE" | "ESC" => Some(Keycode::Escape),
+        "INSERT" => Some(Keycode::Insert),
+        "DELETE" => Some(Keycode::Delete),
+        "HOME" => Some(Keycode::Home),
+        "END" => Some(Keycode::End),
+        "PAGEUP" => Some(Keycode::PageUp),
+        "PAGEDOWN" => Some(Keycode::PageDown),
+        "UP" | "ARROWUP" => Some(Keycode::Up),
+        "DOWN" | "ARROWDOWN" => Some(Keycode::Down),
+        "LEFT" | "ARROWLEFT" => Some(Keycode::Left),
+        "RIGHT" | "ARROWRIGHT" => Some(Keycode::Right),
         _ => None,
     }
 }
 
-pub fn check_hotkey(pressed_keys: &Vec<Keycode>, hotkey_string: &str) -> bool {
-    if hotkey_string.is_empty() {
-        return false;
+/// Inverse of `keycode_from_string` for non-modifier keys: the canonical name a
+/// captured key is stored under. Keys the parser doesn't know return `None`.
+pub fn keycode_name(keycode: Keycode) -> Option<&'static str> {
+    let name = match keycode {
+        Keycode::A => "A",
+        Keycode::B => "B",
+        Keycode::C => "C",
+        Keycode::D => "D",
+        Keycode::E => "E",
+        Keycode::F => "F",
+        Keycode::G => "G",
+        Keycode::H => "H",
+        Keycode::I => "I",
+        Keycode::J => "J",
+        Keycode::K => "K",
+        Keycode::L => "L",
+        Keycode::M => "M",
+        Keycode::N => "N",
+        Keycode::O => "O",
+        Keycode::P => "P",
+        Keycode::Q => "Q",
+        Keycode::R => "R",
+        Keycode::S => "S",
+        Keycode::T => "T",
+        Keycode::U => "U",
+        Keycode::V => "V",
+        Keycode::W => "W",
+        Keycode::X => "X",
+        Keycode::Y => "Y",
+        Keycode::Z => "Z",
+        Keycode::Key0 => "0",
+        Keycode::Key1 => "1",
+        Keycode::Key2 => "2",
+        Keycode::Key3 => "3",
+        Keycode::Key4 => "4",
+        Keycode::Key5 => "5",
+        Keycode::Key6 => "6",
+        Keycode::Key7 => "7",
+        Keycode::Key8 => "8",
+        Keycode::Key9 => "9",
+        Keycode::F1 => "F1",
+        Keycode::F2 => "F2",
+        Keycode::F3 => "F3",
+        Keycode::F4 => "F4",
+        Keycode::F5 => "F5",
+        Keycode::F6 => "F6",
+        Keycode::F7 => "F7",
+        Keycode::F8 => "F8",
+        Keycode::F9 => "F9",
+        Keycode::F10 => "F10",
+        Keycode::F11 => "F11",
+        Keycode::F12 => "F12",
+        Keycode::Space => "Space",
+        Keycode::Enter => "Enter",
+        Keycode::Tab => "Tab",
+        Keycode::Backspace => "Backspace",
+        Keycode::CapsLock => "CapsLock",
+        Keycode::Escape => "Escape",
+        Keycode::Insert => "Insert",
+        Keycode::Delete => "Delete",
+        Keycode::Home => "Home",
+        Keycode::End => "End",
+        Keycode::PageUp => "PageUp",
+        Keycode::PageDown => "PageDown",
+        Keycode::Up => "Up",
+        Keycode::Down => "Down",
+        Keycode::Left => "Left",
+        Keycode::Right => "Right",
+        _ => return None,
+    };
+    Some(name)
+}
+
+pub fn is_modifier(keycode: Keycode) -> bool {
+    matches!(
+        keycode,
+        Keycode::LShift
+            | Keycode::RShift
+            | Keycode::LControl
+            | Keycode::RControl
+            | Keycode::LAlt
+            | Keycode::RAlt
+    )
+}
+
+pub fn get_mouse_button_index(hotkey_str: &str) -> Option<usize> {
+    match hotkey_str {
+        "MouseButton4" => Some(4),
+        "MouseButton5" => Some(5),
+        _ => None,
     }
+}
 
-    let required_key_strings: Vec<&str> = hotkey_string.split('+').map(|s| s.trim()).collect();
-    let mut required_non_modifier_keycodes: Vec<Keycode> = Vec::new();
-    let mut require_shift = false;
-    let mut require_ctrl = false;
-    let mut require_alt = false;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyParseError {
+    Empty,
+    UnknownKey(String),
+    ModifierOnly,
+}
 
-    for key_str in required_key_strings {
-        match key_str.to_uppercase().as_str() {
-            "SHIFT" => require_shift = true,
-            "CTRL" | "CONTROL" => require_ctrl = true,
-            "ALT" => require_alt = true,
-            _ => {
-                // Normal key
-                match keycode_from_string(key_str) {
-                    Some(kc) => required_non_modifier_keycodes.push(kc),
-                    None => {
-                        eprintln!("Warning: Unknown key in hotkey string: {}", key_str);
-                        return false; // Unknown key means hotkey can't be matched
-                    }
+impl std::fmt::Display for HotkeyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HotkeyParseError::Empty => write!(f, "hotkey is empty"),
+            HotkeyParseError::UnknownKey(key) => write!(f, "unknown key: {}", key),
+            HotkeyParseError::ModifierOnly => write!(f, "hotkey has no key besides modifiers"),
+        }
+    }
+}
+
+/// A hotkey string broken down into what the listener actually has to see pressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsedHotkey {
+    MouseButton(usize),
+    Chord {
+        shift: bool,
+        ctrl: bool,
+        alt: bool,
+        keys: Vec<Keycode>,
+    },
+}
+
+impl ParsedHotkey {
+    pub fn matches(&self, pressed_keys: &[Keycode]) -> bool {
+        let ParsedHotkey::Chord {
+            shift,
+            ctrl,
+            alt,
+            keys,
+        } = self
+        else {
+            return false;
+        };
+
+        if *shift
+            && !pressed_keys.contains(&Keycode::LShift)
+            && !pressed_keys.contains(&Keycode::RShift)
+        {
+            return false;
+        }
+        if *ctrl
+            && !pressed_keys.contains(&Keycode::LControl)
+            && !pressed_keys.contains(&Keycode::RControl)
+        {
+            return false;
+        }
+        if *alt && !pressed_keys.contains(&Keycode::LAlt) && !pressed_keys.contains(&Keycode::RAlt)
+        {
+            return false;
+        }
+
+        keys.iter().all(|kc| pressed_keys.contains(kc))
+    }
+
+    /// The keys that have to be held down to trigger this hotkey, with modifiers
+    /// expanded to their left-hand variant.
+    pub fn pressed_keys(&self) -> Vec<Keycode> {
+        let ParsedHotkey::Chord {
+            shift,
+            ctrl,
+            alt,
+            keys,
+        } = self
+        else {
+            return Vec::new();
+        };
+
+        let mut pressed = Vec::new();
+        if *shift {
+            pressed.push(Keycode::LShift);
+        }
+        if *ctrl {
+            pressed.push(Keycode::LControl);
+        }
+        if *alt {
+            pressed.push(Keycode::LAlt);
+        }
+        pressed.extend(keys.iter().copied());
+        pressed
+    }
+
+    /// Builds the chord for a set of currently held keys. Left and right modifiers
+    /// collapse into the generic `Ctrl`/`Alt`/`Shift` that `check_hotkey` matches,
+    /// and keys without a canonical name are dropped.
+    pub fn from_pressed_keys(pressed_keys: &[Keycode]) -> Self {
+        let held = |a: Keycode, b: Keycode| pressed_keys.contains(&a) || pressed_keys.contains(&b);
+        let mut keys: Vec<Keycode> = pressed_keys
+            .iter()
+            .copied()
+            .filter(|kc| keycode_name(*kc).is_some())
+            .collect();
+        keys.dedup();
+        ParsedHotkey::Chord {
+            shift: held(Keycode::LShift, Keycode::RShift),
+            ctrl: held(Keycode::LControl, Keycode::RControl),
+            alt: held(Keycode::LAlt, Keycode::RAlt),
+            keys,
+        }
+    }
+
+    /// Whether pressing `other` also fires `self`. `check_hotkey` ignores extra
+    /// keys, so `F5` is triggered by `Ctrl+F5` but not the other way around.
+    pub fn is_triggered_by(&self, other: &ParsedHotkey) -> bool {
+        match (self, other) {
+            (ParsedHotkey::MouseButton(a), ParsedHotkey::MouseButton(b)) => a == b,
+            (ParsedHotkey::Chord { .. }, ParsedHotkey::Chord { .. }) => {
+                self.matches(&other.pressed_keys())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ParsedHotkey {
+    /// Canonical form: `Ctrl`, `Alt`, `Shift` first, then keys by their `keycode_name`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParsedHotkey::MouseButton(index) => write!(f, "MouseButton{}", index),
+            ParsedHotkey::Chord {
+                shift,
+                ctrl,
+                alt,
+                keys,
+            } => {
+                let mut parts: Vec<String> = Vec::new();
+                if *ctrl {
+                    parts.push("Ctrl".to_string());
+                }
+                if *alt {
+                    parts.push("Alt".to_string());
+                }
+                if *shift {
+                    parts.push("Shift".to_string());
                 }
+                for kc in keys {
+                    parts.push(match keycode_name(*kc) {
+                        Some(name) => name.to_string(),
+                        None => kc.to_string(),
+                    });
+                }
+                write!(f, "{}", parts.join("+"))
             }
         }
     }
+}
 
-    if require_shift
-        && !pressed_keys.contains(&Keycode::LShift)
-        && !pressed_keys.contains(&Keycode::RShift)
-    {
-        return false;
+pub fn parse_hotkey(hotkey_string: &str) -> Result<ParsedHotkey, HotkeyParseError> {
+    let hotkey_string = hotkey_string.trim();
+    if hotkey_string.is_empty() {
+        return Err(HotkeyParseError::Empty);
+    }
+    if let Some(index) = get_mouse_button_index(hotkey_string) {
+        return Ok(ParsedHotkey::MouseButton(index));
     }
-    if require_ctrl
-        && !pressed_keys.contains(&Keycode::LControl)
-        && !pressed_keys.contains(&Keycode::RControl)
-    {
-        return false;
+
+    let mut keys: Vec<Keycode> = Vec::new();
+    let mut shift = false;
+    let mut ctrl = false;
+    let mut alt = false;
+
+    for key_str in hotkey_string.split('+').map(|s| s.trim()) {
+        match key_str.to_uppercase().as_str() {
+            "SHIFT" => shift = true,
+            "CTRL" | "CONTROL" => ctrl = true,
+            "ALT" => alt = true,
+            _ => match keycode_from_string(key_str) {
+                Some(kc) => {
+                    if !keys.contains(&kc) {
+                        keys.push(kc);
+                    }
+                }
+                None => return Err(HotkeyParseError::UnknownKey(key_str.to_string())),
+            },
+        }
     }
-    if require_alt
-        && !pressed_keys.contains(&Keycode::LAlt)
-        && !pressed_keys.contains(&Keycode::RAlt)
-    {
-        return false;
+
+    if keys.is_empty() {
+        return Err(HotkeyParseError::ModifierOnly);
     }
 
-    for kc in &required_non_modifier_keycodes {
-        if !pressed_keys.contains(kc) {
-            return false;
+    Ok(ParsedHotkey::Chord {
+        shift,
+        ctrl,
+        alt,
+        keys,
+    })
+}
+
+pub fn check_hotkey(pressed_keys: &[Keycode], hotkey_string: &str) -> bool {
+    match parse_hotkey(hotkey_string) {
+        Ok(hotkey) => hotkey.matches(pressed_keys),
+        Err(HotkeyParseError::UnknownKey(key)) => {
+            if WARNED_UNKNOWN_KEYS.lock_unpoisoned().insert(key.clone()) {
+                log::warn!(target: target::HOTKEYS, "Unknown key in hotkey string: {}", key);
+            }
+            false // Unknown key means hotkey can't be matched
         }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_browser_style_hotkeys_into_the_canonical_form() {
+        assert_eq!(parse_hotkey("Control+a").unwrap().to_string(), "Ctrl+A");
+        assert_eq!(
+            parse_hotkey("shift + ArrowUp").unwrap().to_string(),
+            "Shift+Up"
+        );
+        assert_eq!(
+            parse_hotkey("MouseButton4").unwrap(),
+            ParsedHotkey::MouseButton(4)
+        );
     }
 
-    if required_non_modifier_keycodes.is_empty() && (require_shift || require_ctrl || require_alt) {
-        return false;
+    #[test]
+    fn extra_keys_still_trigger_a_hotkey() {
+        let f5 = parse_hotkey("F5").unwrap();
+        let ctrl_f5 = parse_hotkey("Ctrl+F5").unwrap();
+        assert!(f5.is_triggered_by(&ctrl_f5));
+        assert!(!ctrl_f5.is_triggered_by(&f5));
+        assert!(ctrl_f5.matches(&[Keycode::RControl, Keycode::F5]));
+        assert!(!ctrl_f5.matches(&[Keycode::F5]));
     }
 
-    !required_non_modifier_keycodes.is_empty()
+    #[test]
+    fn rejects_modifier_only_and_unknown_keys() {
+        assert_eq!(
+            parse_hotkey("Ctrl+Shift"),
+            Err(HotkeyParseError::ModifierOnly)
+        );
+        assert_eq!(
+            parse_hotkey("Ctrl+Hyper"),
+            Err(HotkeyParseError::UnknownKey("Hyper".to_string()))
+        );
+        assert_eq!(parse_hotkey("  "), Err(HotkeyParseError::Empty));
+    }
 }
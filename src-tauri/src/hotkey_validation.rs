@@ -0,0 +1,205 @@
+use device_query::Keycode;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+use crate::hotkey_utils::{self, HotkeyParseError, ParsedHotkey};
+
+/// Shortcuts the OS or window manager already reacts to. A binding that fires
+/// on one of these toggles the clicker every time the user switches windows etc.
+const SYSTEM_SHORTCUTS: &[(&str, &[Keycode])] = &[
+    ("Alt+Tab", &[Keycode::LAlt, Keycode::Tab]),
+    ("Alt+F4", &[Keycode::LAlt, Keycode::F4]),
+    ("Alt+Space", &[Keycode::LAlt, Keycode::Space]),
+    (
+        "Ctrl+Alt+Delete",
+        &[Keycode::LControl, Keycode::LAlt, Keycode::Delete],
+    ),
+    (
+        "Ctrl+Shift+Escape",
+        &[Keycode::LControl, Keycode::LShift, Keycode::Escape],
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueKind {
+    UnknownKey,
+    ModifierOnly,
+    Duplicate,
+    Subset,
+    SystemShortcut,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyIssue {
+    pub kind: IssueKind,
+    pub severity: IssueSeverity,
+    pub bindings: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyValidation {
+    pub valid: bool,
+    pub issues: Vec<HotkeyIssue>,
+}
+
+impl HotkeyValidation {
+    fn push(
+        &mut self,
+        kind: IssueKind,
+        severity: IssueSeverity,
+        bindings: &[&str],
+        message: String,
+    ) {
+        self.issues.push(HotkeyIssue {
+            kind,
+            severity,
+            bindings: bindings.iter().map(|b| b.to_string()).collect(),
+            message,
+        });
+    }
+}
+
+/// Checks a set of bindings (store key -> hotkey string) against each other and
+/// against what the listener can actually match. Empty hotkeys are unbound and skipped.
+pub fn validate_bindings(bindings: &BTreeMap<String, String>) -> HotkeyValidation {
+    let mut result = HotkeyValidation::default();
+    let mut parsed: Vec<(&str, &str, ParsedHotkey)> = Vec::new();
+
+    for (name, hotkey) in bindings {
+        match hotkey_utils::parse_hotkey(hotkey) {
+            Ok(p) => parsed.push((name, hotkey, p)),
+            Err(HotkeyParseError::Empty) => {}
+            Err(HotkeyParseError::UnknownKey(key)) => result.push(
+                IssueKind::UnknownKey,
+                IssueSeverity::Error,
+                &[name],
+                format!(
+                    "\"{}\" contains a key that cannot be detected: {}",
+                    hotkey, key
+                ),
+            ),
+            Err(HotkeyParseError::ModifierOnly) => result.push(
+                IssueKind::ModifierOnly,
+                IssueSeverity::Error,
+                &[name],
+                format!("\"{}\" needs a key besides Ctrl, Alt and Shift", hotkey),
+            ),
+        }
+    }
+
+    for (i, (name_a, hotkey_a, a)) in parsed.iter().enumerate() {
+        for (name_b, hotkey_b, b) in parsed.iter().skip(i + 1) {
+            let a_by_b = a.is_triggered_by(b);
+            let b_by_a = b.is_triggered_by(a);
+            if a_by_b && b_by_a {
+                result.push(
+                    IssueKind::Duplicate,
+                    IssueSeverity::Error,
+                    &[name_a, name_b],
+                    format!("\"{}\" and \"{}\" are the same hotkey", hotkey_a, hotkey_b),
+                );
+            } else if a_by_b || b_by_a {
+                let (inner, outer) = if a_by_b {
+                    (hotkey_a, hotkey_b)
+                } else {
+                    (hotkey_b, hotkey_a)
+                };
+                result.push(
+                    IssueKind::Subset,
+                    IssueSeverity::Warning,
+                    &[name_a, name_b],
+                    format!("pressing \"{}\" also triggers \"{}\"", outer, inner),
+                );
+            }
+        }
+
+        for (shortcut, keys) in SYSTEM_SHORTCUTS {
+            if a.matches(keys) {
+                result.push(
+                    IssueKind::SystemShortcut,
+                    IssueSeverity::Warning,
+                    &[name_a],
+                    format!(
+                        "\"{}\" is also triggered by the system shortcut {}",
+                        hotkey_a, shortcut
+                    ),
+                );
+            }
+        }
+    }
+
+    result.valid = !result
+        .issues
+        .iter()
+        .any(|issue| issue.severity == IssueSeverity::Error);
+    result
+}
+
+#[tauri::command]
+pub fn validate_hotkeys(bindings: BTreeMap<String, String>) -> HotkeyValidation {
+    validate_bindings(&bindings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(bindings: &[(&str, &str)]) -> HotkeyValidation {
+        validate_bindings(
+            &bindings
+                .iter()
+                .map(|(name, hotkey)| (name.to_string(), hotkey.to_string()))
+                .collect(),
+        )
+    }
+
+    fn kinds(validation: &HotkeyValidation) -> Vec<IssueKind> {
+        validation.issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn warns_when_one_hotkey_contains_another() {
+        let validation = validate(&[("hotkeyLeft", "F5"), ("hotkeyRight", "Ctrl+F5")]);
+        assert!(validation.valid);
+        assert_eq!(kinds(&validation), [IssueKind::Subset]);
+        assert_eq!(validation.issues[0].severity, IssueSeverity::Warning);
+        assert_eq!(
+            validation.issues[0].message,
+            "pressing \"Ctrl+F5\" also triggers \"F5\""
+        );
+    }
+
+    #[test]
+    fn rejects_the_same_hotkey_twice() {
+        let validation = validate(&[("hotkeyLeft", "Ctrl+F5"), ("panicHotkey", "control+f5")]);
+        assert!(!validation.valid);
+        assert_eq!(kinds(&validation), [IssueKind::Duplicate]);
+        assert_eq!(validation.issues[0].bindings, ["hotkeyLeft", "panicHotkey"]);
+    }
+
+    #[test]
+    fn rejects_modifier_only_and_unknown_keys() {
+        let validation = validate(&[
+            ("hotkeyLeft", "Ctrl+Alt"),
+            ("hotkeyRight", "Hyper"),
+            ("panicHotkey", ""),
+        ]);
+        assert!(!validation.valid);
+        assert_eq!(
+            kinds(&validation),
+            [IssueKind::ModifierOnly, IssueKind::UnknownKey]
+        );
+    }
+}
@@ -1,25 +1,56 @@
 use device_query::{DeviceQuery, DeviceState, Keycode};
-use enigo::{Button, Direction::Click, Enigo, Mouse, Settings};
-use std::sync::{Arc, Mutex};
+use enigo::{Direction::Click, Mouse};
 use std::thread;
-use std::time::Duration;
-use tauri::Emitter;
+use std::time::{Duration, Instant};
+use tauri::{Manager, RunEvent, State};
 use tauri_store::ManagerExt;
 
+mod audio;
+mod backend;
+#[cfg(test)]
+mod bindings;
+mod cli;
+mod clicker;
+mod clicker_state;
+mod diagnostics;
+mod failsafe;
+mod history;
+mod hotkey_capture;
 mod hotkey_utils;
+mod hotkey_validation;
+mod logging;
+mod notifications;
+mod overlay;
+mod profiles;
+mod settings;
+mod stats;
 mod store_keys;
-
-use crate::store_keys::{autoclicker_keys, store, temp_keys};
-
-fn get_mouse_button_index(hotkey_str: &str) -> Option<usize> {
-    match hotkey_str {
-        "MouseButton4" => Some(4),
-        "MouseButton5" => Some(5),
-        _ => None,
-    }
-}
-
-fn is_mouse_button_pressed(mouse_buttons: &Vec<bool>, hotkey_str: &str) -> bool {
+mod supervisor;
+mod takeover;
+mod transfer;
+mod tray;
+
+use crate::audio::AudioCues;
+use crate::backend::InputBackend;
+use crate::clicker::{Channel, ClickSchedule};
+use crate::clicker_state::{ClickerState, StopReason};
+use crate::failsafe::Failsafe;
+use crate::history::SessionHistory;
+use crate::hotkey_capture::{CaptureSession, HotkeyCapture};
+use crate::hotkey_utils::get_mouse_button_index;
+use crate::logging::target;
+use crate::settings::{AutoclickerSettings, SharedSettings};
+use crate::stats::StatsTracker;
+use crate::store_keys::store;
+use crate::supervisor::{Supervisor, Worker};
+use crate::takeover::CursorGuard;
+
+/// Clicks failing this many times in a row mean the input backend is gone.
+const MAX_CONSECUTIVE_CLICK_FAILURES: u32 = 10;
+/// How long exit waits for the worker threads to finish.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn is_mouse_button_pressed(mouse_buttons: &[bool], hotkey_str: &str) -> bool {
     match get_mouse_button_index(hotkey_str) {
         Some(index) => mouse_buttons.get(index).cloned().unwrap_or(false),
         _ => false,
@@ -27,8 +58,8 @@ fn is_mouse_button_pressed(mouse_buttons: &Vec<bool>, hotkey_str: &str) -> bool
 }
 
 fn was_mouse_button_just_pressed(
-    current_buttons: &Vec<bool>,
-    previous_buttons: &Vec<bool>,
+    current_buttons: &[bool],
+    previous_buttons: &[bool],
     hotkey_str: &str,
 ) -> bool {
     if let Some(index) = get_mouse_button_index(hotkey_str) {
@@ -40,155 +71,80 @@ fn was_mouse_button_just_pressed(
     }
 }
 
+fn is_hotkey_pressed(
+    current_keys: &[Keycode],
+    current_mouse_buttons: &[bool],
+    hotkey_str: &str,
+) -> bool {
+    if get_mouse_button_index(hotkey_str).is_some() {
+        is_mouse_button_pressed(current_mouse_buttons, hotkey_str)
+    } else {
+        hotkey_utils::check_hotkey(current_keys, hotkey_str)
+    }
+}
+
 struct HotkeyManager {
     app_handle: tauri::AppHandle,
-    is_running: Arc<Mutex<bool>>,
-    hotkey_left: Arc<Mutex<String>>,
-    hotkey_right: Arc<Mutex<String>>,
-    hold_mode: Arc<Mutex<bool>>,
 }
 
 impl HotkeyManager {
     fn new(app_handle: tauri::AppHandle) -> Self {
-        let initial_is_running = app_handle
-            .store_collection()
-            .get::<bool>(store::TEMP, temp_keys::IS_RUNNING)
-            .unwrap_or(false);
-        let initial_hotkey_left = app_handle
-            .store_collection()
-            .get::<String>(store::AUTOCLICKER, autoclicker_keys::HOTKEY_LEFT)
-            .unwrap_or_default();
-        let initial_hotkey_right = app_handle
-            .store_collection()
-            .get::<String>(store::AUTOCLICKER, autoclicker_keys::HOTKEY_RIGHT)
-            .unwrap_or_default();
-        let initial_hold_mode = app_handle
-            .store_collection()
-            .get::<bool>(store::AUTOCLICKER, autoclicker_keys::HOLD_MODE)
-            .unwrap_or(false);
-
-        let is_running = Arc::new(Mutex::new(initial_is_running));
-        let hotkey_left = Arc::new(Mutex::new(initial_hotkey_left));
-        let hotkey_right = Arc::new(Mutex::new(initial_hotkey_right));
-        let hold_mode = Arc::new(Mutex::new(initial_hold_mode));
-
-        let is_running_clone = Arc::clone(&is_running);
-        let _ = app_handle.store_collection().watch(store::TEMP, move |app| {
-            if let Ok(new_val) = app.store_collection().get::<bool>(store::TEMP, temp_keys::IS_RUNNING) {
-                *is_running_clone.lock().unwrap() = new_val;
-            }
-            Ok(())
-        });
-
-        let hotkey_left_clone = Arc::clone(&hotkey_left);
-        let hotkey_right_clone = Arc::clone(&hotkey_right);
-        let hold_mode_clone = Arc::clone(&hold_mode);
-        let _ = app_handle.store_collection().watch(store::AUTOCLICKER, move |app| {
-            if let Ok(new_val) = app.store_collection().get::<String>(store::AUTOCLICKER, autoclicker_keys::HOTKEY_LEFT) {
-                *hotkey_left_clone.lock().unwrap() = new_val;
-            }
-            if let Ok(new_val) = app.store_collection().get::<String>(store::AUTOCLICKER, autoclicker_keys::HOTKEY_RIGHT) {
-                *hotkey_right_clone.lock().unwrap() = new_val;
-            }
-            if let Ok(new_val) = app.store_collection().get::<bool>(store::AUTOCLICKER, autoclicker_keys::HOLD_MODE) {
-                *hold_mode_clone.lock().unwrap() = new_val;
-            }
-            Ok(())
-        });
-
-        Self {
-            app_handle,
-            is_running,
-            hotkey_left,
-            hotkey_right,
-            hold_mode,
-        }
+        Self { app_handle }
     }
 
-    fn start(self) {
-        thread::spawn(move || {
-            self.process_hotkeys_loop();
-        });
+    fn state(&self) -> State<'_, ClickerState> {
+        self.app_handle.state::<ClickerState>()
     }
 
-    fn update_hotkey_state(
-        &self,
-        store_key: &'static str,
-        new_active_state: bool,
-        emit_event_name: &'static str,
-        mode_description: &str,
-    ) {
-        if let Err(e) = self.app_handle.store_collection().set(store::TEMP, store_key, new_active_state) {
-            eprintln!(
-                "Failed to set {} ({}) in store: {}",
-                store_key, mode_description, e
-            );
+    /// Applies a channel change from a hotkey. The loop only gets here while
+    /// running, so losing a race against a stop is the only possible error.
+    fn set_channel(&self, channel: Channel, active: Option<bool>) {
+        if let Err(e) = self.state().set_channel(channel, active) {
+            log::debug!(target: target::HOTKEYS, "Ignored {:?} hotkey: {}", channel, e);
         }
-        self.app_handle
-            .emit(emit_event_name, new_active_state)
-            .unwrap_or_else(|e| {
-                eprintln!(
-                    "Failed to emit {} ({}): {}",
-                    emit_event_name, mode_description, e
-                );
-            });
     }
 
-    fn handle_hold_mode(&self, current_keys: &Vec<Keycode>, current_mouse_buttons: &Vec<bool>) {
-        let hotkey_left_str = self.hotkey_left.lock().unwrap().clone();
-        let hotkey_right_str = self.hotkey_right.lock().unwrap().clone();
-
-        if !hotkey_left_str.is_empty() {
-            let left_hotkey_is_active = if get_mouse_button_index(&hotkey_left_str).is_some() {
-                is_mouse_button_pressed(&current_mouse_buttons, &hotkey_left_str)
-            } else {
-                hotkey_utils::check_hotkey(&current_keys, &hotkey_left_str)
-            };
-            let current_left_active_in_store = self.app_handle
-                .store_collection()
-                .get::<bool>(store::TEMP, temp_keys::HOTKEY_LEFT_ACTIVE)
-                .unwrap_or(false);
-            if left_hotkey_is_active != current_left_active_in_store {
-                self.update_hotkey_state(
-                    temp_keys::HOTKEY_LEFT_ACTIVE,
-                    left_hotkey_is_active,
-                    "left-hotkey-activated",
-                    "hold",
-                );
+    /// Letting go of a hotkey stops its channel; pressing it starts it. Only a
+    /// fresh press starts it, so a channel the mouse takeover stopped stays off
+    /// until the hotkey is pressed again.
+    fn handle_hold_mode(
+        &self,
+        settings: &AutoclickerSettings,
+        current_keys: &[Keycode],
+        current_mouse_buttons: &[bool],
+        previous_keys: &[Keycode],
+        previous_mouse_buttons: &[bool],
+    ) {
+        for (channel, hotkey) in [
+            (Channel::Left, &settings.hotkey_left),
+            (Channel::Right, &settings.hotkey_right),
+        ] {
+            if hotkey.is_empty() {
+                continue;
             }
-        }
-
-        if !hotkey_right_str.is_empty() {
-            let right_hotkey_is_active = if get_mouse_button_index(&hotkey_right_str).is_some() {
-                is_mouse_button_pressed(&current_mouse_buttons, &hotkey_right_str)
-            } else {
-                hotkey_utils::check_hotkey(&current_keys, &hotkey_right_str)
-            };
-            let current_right_active_in_store = self.app_handle
-                .store_collection()
-                .get::<bool>(store::TEMP, temp_keys::HOTKEY_RIGHT_ACTIVE)
-                .unwrap_or(false);
-            if right_hotkey_is_active != current_right_active_in_store {
-                self.update_hotkey_state(
-                    temp_keys::HOTKEY_RIGHT_ACTIVE,
-                    right_hotkey_is_active,
-                    "right-hotkey-activated",
-                    "hold",
-                );
+            let held = is_hotkey_pressed(current_keys, current_mouse_buttons, hotkey);
+            let was_held = is_hotkey_pressed(previous_keys, previous_mouse_buttons, hotkey);
+            if held && !was_held {
+                self.set_channel(channel, Some(true));
+            } else if !held && self.state().is_active(channel) {
+                self.set_channel(channel, Some(false));
             }
         }
     }
 
     fn handle_toggle_mode(
         &self,
-        current_keys: &Vec<Keycode>,
-        current_mouse_buttons: &Vec<bool>,
+        settings: &AutoclickerSettings,
+        current_keys: &[Keycode],
+        current_mouse_buttons: &[bool],
         previous_keys: &mut Vec<Keycode>,
         previous_mouse_buttons: &mut Vec<bool>,
     ) {
-        if current_keys != previous_keys || current_mouse_buttons != previous_mouse_buttons {
-            let hotkey_left_str = self.hotkey_left.lock().unwrap().clone();
-            let hotkey_right_str = self.hotkey_right.lock().unwrap().clone();
+        if current_keys != previous_keys.as_slice()
+            || current_mouse_buttons != previous_mouse_buttons.as_slice()
+        {
+            let hotkey_left_str = settings.hotkey_left.clone();
+            let hotkey_right_str = settings.hotkey_right.clone();
 
             if !hotkey_left_str.is_empty() {
                 let mut triggered = false;
@@ -201,23 +157,14 @@ impl HotkeyManager {
                         triggered = true;
                     }
                 } else {
-                    if hotkey_utils::check_hotkey(&current_keys, &hotkey_left_str)
+                    if hotkey_utils::check_hotkey(current_keys, &hotkey_left_str)
                         && !hotkey_utils::check_hotkey(previous_keys, &hotkey_left_str)
                     {
                         triggered = true;
                     }
                 }
                 if triggered {
-                    let current_left_active = self.app_handle
-                        .store_collection()
-                        .get::<bool>(store::TEMP, temp_keys::HOTKEY_LEFT_ACTIVE)
-                        .unwrap_or(false);
-                    self.update_hotkey_state(
-                        temp_keys::HOTKEY_LEFT_ACTIVE,
-                        !current_left_active,
-                        "left-hotkey-activated",
-                        "toggle",
-                    );
+                    self.set_channel(Channel::Left, None);
                 }
             }
 
@@ -232,214 +179,351 @@ impl HotkeyManager {
                         triggered = true;
                     }
                 } else {
-                    if hotkey_utils::check_hotkey(&current_keys, &hotkey_right_str)
+                    if hotkey_utils::check_hotkey(current_keys, &hotkey_right_str)
                         && !hotkey_utils::check_hotkey(previous_keys, &hotkey_right_str)
                     {
                         triggered = true;
                     }
                 }
                 if triggered {
-                    let current_right_active = self.app_handle
-                        .store_collection()
-                        .get::<bool>(store::TEMP, temp_keys::HOTKEY_RIGHT_ACTIVE)
-                        .unwrap_or(false);
-                    self.update_hotkey_state(
-                        temp_keys::HOTKEY_RIGHT_ACTIVE,
-                        !current_right_active,
-                        "right-hotkey-activated",
-                        "toggle",
-                    );
+                    self.set_channel(Channel::Right, None);
                 }
             }
-            *previous_keys = current_keys.clone();
-            *previous_mouse_buttons = current_mouse_buttons.clone();
+            *previous_keys = current_keys.to_vec();
+            *previous_mouse_buttons = current_mouse_buttons.to_vec();
         }
     }
 
-    fn reset_hold_mode_hotkeys(&self) {
-        if self.app_handle
-            .store_collection()
-            .get::<bool>(store::TEMP, temp_keys::HOTKEY_LEFT_ACTIVE)
-            .unwrap_or(false)
-        {
-            self.update_hotkey_state(
-                temp_keys::HOTKEY_LEFT_ACTIVE,
-                false,
-                "left-hotkey-activated",
-                "reset (hold)",
-            );
+    /// Stops active channels once the physical cursor has moved further than the
+    /// configured threshold since the last click.
+    fn check_user_takeover(&self, settings: &AutoclickerSettings, coords: (i32, i32)) {
+        let guard = self.app_handle.state::<CursorGuard>();
+        let threshold = settings.mouse_move_threshold;
+        let any_active = self.state().phase().channels().any();
+
+        if threshold == 0 || !any_active {
+            guard.reset();
+            return;
         }
-        if self.app_handle
-            .store_collection()
-            .get::<bool>(store::TEMP, temp_keys::HOTKEY_RIGHT_ACTIVE)
-            .unwrap_or(false)
-        {
-            self.update_hotkey_state(
-                temp_keys::HOTKEY_RIGHT_ACTIVE,
-                false,
-                "right-hotkey-activated",
-                "reset (hold)",
-            );
+        if guard.moved_beyond(coords, threshold) {
+            guard.reset();
+            self.state().stop_channels(StopReason::UserTookOver);
+        }
+    }
+
+    /// Switches to the first inactive profile whose switch hotkey is held. Returns
+    /// whether any profile's switch hotkey is held, for edge detection.
+    fn handle_profile_switch(
+        &self,
+        settings: &AutoclickerSettings,
+        current_keys: &[Keycode],
+        current_mouse_buttons: &[bool],
+    ) -> bool {
+        let pressed = settings.profiles.iter().find(|(_, profile)| {
+            !profile.switch_hotkey.is_empty()
+                && is_hotkey_pressed(current_keys, current_mouse_buttons, &profile.switch_hotkey)
+        });
+        let Some((name, _)) = pressed else {
+            return false;
+        };
+        if *name != settings.active_profile {
+            if let Err(e) = profiles::switch_profile(self.app_handle.clone(), name.clone()) {
+                log::error!(target: target::HOTKEYS, "Failed to switch to profile {}: {}", name, e);
+            }
         }
+        true
     }
 
     fn process_hotkeys_loop(&self) {
         let device_state = DeviceState::new();
         let mut previous_keys = device_state.get_keys();
         let mut previous_mouse_buttons = device_state.get_mouse().button_pressed;
+        let mut panic_was_pressed = false;
+        let mut failsafe = Failsafe::default();
+        let mut switch_hotkey_was_pressed = false;
+
+        let mut capture_session: Option<CaptureSession> = None;
+
+        while !self.state().is_shutting_down() {
+            let settings = self.app_handle.state::<SharedSettings>().snapshot();
+            let current_keys = device_state.get_keys();
+            let mouse = device_state.get_mouse();
+            let current_mouse_buttons = mouse.button_pressed;
+
+            let capture = self.app_handle.state::<HotkeyCapture>();
+            let pending_capture = capture.pending();
+            if capture_session.as_ref().map(|session| session.id) != pending_capture {
+                capture_session = pending_capture.map(|id| {
+                    CaptureSession::new(id, &previous_keys, &previous_mouse_buttons, Instant::now())
+                });
+            }
+            if let Some(session) = capture_session.as_mut() {
+                let now = Instant::now();
+                if let Some(result) = session.poll(&current_keys, &current_mouse_buttons, now) {
+                    capture.finish(session.id, result);
+                    capture_session = None;
+                }
+            }
+
+            let panic_hotkey_str = &settings.panic_hotkey;
+            let panic_pressed = !panic_hotkey_str.is_empty()
+                && is_hotkey_pressed(&current_keys, &current_mouse_buttons, panic_hotkey_str);
+            if panic_pressed {
+                if !panic_was_pressed {
+                    self.state().stop(StopReason::EmergencyStop);
+                }
+                panic_was_pressed = true;
+                // The panic chord may contain another binding; don't let it fire.
+                previous_keys = current_keys;
+                previous_mouse_buttons = current_mouse_buttons;
+                thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+            panic_was_pressed = false;
+
+            let is_running_val = self.state().is_running();
+
+            if is_running_val
+                && failsafe.is_triggered(&self.app_handle, settings.failsafe_corner, mouse.coords)
+            {
+                self.state().stop(StopReason::Failsafe);
+                continue;
+            }
 
-        loop {
-            let is_running_val = *self.is_running.lock().unwrap();
+            if pending_capture.is_some() {
+                // Whatever is being recorded must not also fire as a binding, and
+                // the captured chord is likely still held on the next poll.
+                if is_running_val {
+                    self.check_user_takeover(&settings, mouse.coords);
+                }
+                previous_keys = current_keys;
+                previous_mouse_buttons = current_mouse_buttons;
+                thread::sleep(hotkey_capture::CAPTURE_POLL_INTERVAL);
+                continue;
+            }
 
             if is_running_val {
-                let current_keys = device_state.get_keys();
-                let current_mouse_buttons = device_state.get_mouse().button_pressed;
-                let hold_mode_val = *self.hold_mode.lock().unwrap();
+                let switch_pressed =
+                    self.handle_profile_switch(&settings, &current_keys, &current_mouse_buttons);
+                if switch_pressed && !switch_hotkey_was_pressed {
+                    switch_hotkey_was_pressed = true;
+                    // The next poll picks up the new profile's hotkeys.
+                    previous_keys = current_keys;
+                    previous_mouse_buttons = current_mouse_buttons;
+                    continue;
+                }
+                switch_hotkey_was_pressed = switch_pressed;
 
-                if hold_mode_val {
-                    self.handle_hold_mode(&current_keys, &current_mouse_buttons);
+                if settings.hold_mode {
+                    self.handle_hold_mode(
+                        &settings,
+                        &current_keys,
+                        &current_mouse_buttons,
+                        &previous_keys,
+                        &previous_mouse_buttons,
+                    );
+                    previous_keys = current_keys;
+                    previous_mouse_buttons = current_mouse_buttons;
                 } else {
                     self.handle_toggle_mode(
+                        &settings,
                         &current_keys,
                         &current_mouse_buttons,
                         &mut previous_keys,
                         &mut previous_mouse_buttons,
                     );
                 }
+                self.check_user_takeover(&settings, mouse.coords);
                 thread::sleep(Duration::from_millis(50));
             } else {
-                if *self.hold_mode.lock().unwrap() {
-                    self.reset_hold_mode_hotkeys();
-                }
                 thread::sleep(Duration::from_millis(200));
             }
         }
     }
 }
 
-fn handle_hotkeys(app_handle_hotkey: tauri::AppHandle) {
-    let manager = HotkeyManager::new(app_handle_hotkey);
-    manager.start();
+fn handle_hotkeys(app_handle_hotkey: &tauri::AppHandle) {
+    HotkeyManager::new(app_handle_hotkey.clone()).process_hotkeys_loop();
 }
 
-fn handle_clicking(app_handle_clicker: tauri::AppHandle) {
-    thread::spawn(move || {
-        let is_running_arc = Arc::new(Mutex::new(false));
-        let left_active_arc = Arc::new(Mutex::new(false));
-        let right_active_arc = Arc::new(Mutex::new(false));
-
-        let is_running_clone = Arc::clone(&is_running_arc);
-        let left_active_clone = Arc::clone(&left_active_arc);
-        let right_active_clone = Arc::clone(&right_active_arc);
-
-        let _ = app_handle_clicker.store_collection().watch(store::TEMP, move |app| {
-            let new_is_running = app
-                .store_collection()
-                .get::<bool>(store::TEMP, temp_keys::IS_RUNNING)
-                .unwrap_or(false);
-
-            let new_left_active = app
-                .store_collection()
-                .get::<bool>(store::TEMP, temp_keys::HOTKEY_LEFT_ACTIVE)
-                .unwrap_or(false);
-
-            let new_right_active = app
-                .store_collection()
-                .get::<bool>(store::TEMP, temp_keys::HOTKEY_RIGHT_ACTIVE)
-                .unwrap_or(false);
-
-            let mut is_running_lock = is_running_clone.lock().unwrap();
-            *is_running_lock = new_is_running;
-
-            let mut left_active_lock = left_active_clone.lock().unwrap();
-            *left_active_lock = new_left_active;
-
-            let mut right_active_lock = right_active_clone.lock().unwrap();
-            *right_active_lock = new_right_active;
-
-            Ok(())
-        });
-
-        let speed_ms_arc = Arc::new(Mutex::new(
-            app_handle_clicker
-                .store_collection()
-                .get::<f64>(store::AUTOCLICKER, autoclicker_keys::CLICK_SPEED)
-                .unwrap_or(100.0),
-        ));
-        let speed_ms_clone = Arc::clone(&speed_ms_arc);
-
-        let _ = app_handle_clicker.store_collection().watch(store::AUTOCLICKER, move |app| {
-            let new_speed_ms = app
-                .store_collection()
-                .get::<f64>(store::AUTOCLICKER, autoclicker_keys::CLICK_SPEED)
-                .unwrap_or(100.0);
-
-            let mut speed_ms_lock = speed_ms_clone.lock().unwrap();
-            *speed_ms_lock = new_speed_ms;
-
-            Ok(())
-        });
-
-        let mut enigo = Enigo::new(&Settings::default()).unwrap();
-
-        #[cfg(target_os = "linux")]
-        enigo.set_delay(0);
-
-        loop {
-            let (speed_ms, is_running, left_active, right_active) = {
-                let speed = *speed_ms_arc.lock().expect("Failed to lock speed mutex");
-                let running = *is_running_arc.lock().expect("Failed to lock running mutex");
-                let left = *left_active_arc
-                    .lock()
-                    .expect("Failed to lock left active mutex");
-                let right = *right_active_arc
-                    .lock()
-                    .expect("Failed to lock right active mutex");
-                (speed, running, left, right)
-            };
-
-            let sleep_duration = Duration::from_micros((speed_ms * 1000.0) as u64);
-
-            if !is_running {
-                thread::sleep(Duration::from_millis(200));
-                continue;
-            }
-
-            if left_active {
-                if let Err(e) = enigo.button(Button::Left, Click) {
-                    eprintln!("Failed to perform left click: {}", e);
+fn handle_clicking(app_handle_clicker: &tauri::AppHandle) {
+    let backend = app_handle_clicker.state::<InputBackend>();
+    let Some(mut enigo) = backend.connect(app_handle_clicker) else {
+        return;
+    };
+    let mut failures = 0;
+
+    let state = app_handle_clicker.state::<ClickerState>();
+    let stats = app_handle_clicker.state::<StatsTracker>();
+    let cursor_guard = app_handle_clicker.state::<CursorGuard>();
+    let mut schedule = ClickSchedule::default();
+    loop {
+        let Some(channels) = state.wait_for_clicking() else {
+            return;
+        };
+        let settings = app_handle_clicker.state::<SharedSettings>().snapshot();
+        let now = Instant::now();
+        for channel in schedule.due(channels, now) {
+            let interval = settings.channel(channel).next_interval();
+            let missed = schedule.clicked(channel, now, interval);
+            match enigo.button(channel.button(), Click) {
+                Ok(()) => {
+                    failures = 0;
+                    stats.record(channel, now, missed);
+                    if let Ok(position) = enigo.location() {
+                        cursor_guard.clicked(position);
+                    }
                 }
-            }
-
-            if right_active {
-                if let Err(e) = enigo.button(Button::Right, Click) {
-                    eprintln!("Failed to perform right click: {}", e);
+                Err(e) if failures + 1 >= MAX_CONSECUTIVE_CLICK_FAILURES => {
+                    failures = 0;
+                    match backend.reconnect(app_handle_clicker, e.to_string()) {
+                        Some(reconnected) => enigo = reconnected,
+                        None => return,
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    log::error!(
+                        target: target::CLICKER,
+                        "Failed to perform {:?} click: {}",
+                        channel,
+                        e
+                    );
                 }
             }
+        }
+        stats.emit_throttled(app_handle_clicker);
 
-            let sleep_time = if left_active || right_active {
-                sleep_duration
-            } else {
-                Duration::from_millis(50)
-            };
-            thread::sleep(sleep_time);
+        if let Some(wake) = schedule.next_wake() {
+            state.wait_while_clicking(channels, wake.saturating_duration_since(Instant::now()));
         }
-    });
+    }
+}
+
+/// Stops clicking, releases held buttons, resets the `temp` store and waits
+/// for the worker threads. Safe to call more than once.
+///
+/// Runs on the main thread, which the tray and overlay updates of the stop run
+/// on right away. Manual check after touching it: start both channels and quit
+/// from the tray menu, then again with the window's close button and minimize
+/// to tray off. Each time the app has to exit within `SHUTDOWN_TIMEOUT`, leave
+/// no button pressed and record the session with `appExit` as its stop reason.
+fn shutdown(app_handle: &tauri::AppHandle) {
+    let Some(state) = app_handle.try_state::<ClickerState>() else {
+        return;
+    };
+    if state.is_shutting_down() {
+        return;
+    }
+    log::info!(target: target::APP, "Shutting down");
+    state.shut_down();
+    app_handle.state::<HotkeyCapture>().cancel();
+    if let Err(e) = app_handle.store_collection().save_now(store::TEMP) {
+        log::error!(target: target::STORE, "Failed to save clicker state on exit: {}", e);
+    }
+    if !app_handle.state::<Supervisor>().join(SHUTDOWN_TIMEOUT) {
+        log::warn!(target: target::APP, "Worker threads didn't stop within {:?}", SHUTDOWN_TIMEOUT);
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
-        .setup(|app| {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse(args.clone()) {
+        Ok(command) => command,
+        Err(e) => {
+            cli::print_usage(&e);
+            std::process::exit(2);
+        }
+    };
+
+    let mut builder = tauri::Builder::default();
+    // Has to be the first plugin. Subcommands check for a running instance
+    // themselves, since the plugin would end them without a word.
+    if command.is_none() {
+        builder = builder.plugin(tauri_plugin_single_instance::init(cli::forward));
+    }
+    builder
+        .setup(move |app| {
             let app_handle = app.handle().clone();
+            app.manage(HotkeyCapture::default());
+            app.manage(CursorGuard::default());
+            app.manage(StatsTracker::default());
+            app.manage(InputBackend::default());
+            app.manage(Supervisor::default());
+            app.manage(AudioCues::default());
+            app.manage(SessionHistory::new(&app_handle));
+            settings::init(&app_handle);
+
+            if let Some(command) = command {
+                std::process::exit(cli::execute(&app_handle, command));
+            }
 
-            handle_hotkeys(app_handle.clone());
-            handle_clicking(app_handle.clone());
+            match cli::InstanceLock::acquire(&app_handle) {
+                Ok(Some(lock)) => {
+                    app.manage(lock);
+                }
+                Ok(None) => log::warn!(target: target::APP, "An export or import is still running"),
+                Err(e) => log::warn!(target: target::APP, "Could not lock the app data dir: {}", e),
+            }
+            app.manage(ClickerState::new(app_handle.clone()));
+
+            supervisor::supervise(app_handle.clone(), Worker::Hotkeys, handle_hotkeys);
+            supervisor::supervise(app_handle.clone(), Worker::Clicker, handle_clicking);
+
+            // Without a tray there'd be no way back to a hidden window.
+            let has_tray = tray::init(&app_handle)
+                .inspect_err(|e| {
+                    log::warn!(target: target::APP, "No system tray available: {}", e)
+                })
+                .is_ok();
+            let start_hidden = app_handle.state::<SharedSettings>().snapshot().start_hidden;
+            if !(start_hidden && has_tray) {
+                tray::show_window(&app_handle);
+            }
+            // Parsed here rather than up front so ignored arguments get logged.
+            cli::apply_controls(&app_handle, cli::parse_controls(args));
 
             Ok(())
         })
+        .on_window_event(|window, event| {
+            tray::on_window_event(window, event);
+            overlay::on_window_event(window, event);
+        })
+        .plugin(logging::plugin())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_store::init())
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            clicker::start,
+            clicker::stop,
+            clicker::toggle_channel,
+            clicker::set_speed,
+            clicker::get_status,
+            backend::get_backend_status,
+            audio::preview_cue,
+            stats::get_stats,
+            history::get_history,
+            history::clear_history,
+            logging::get_recent_logs,
+            diagnostics::collect_diagnostics,
+            hotkey_capture::capture_hotkey,
+            hotkey_validation::validate_hotkeys,
+            profiles::create_profile,
+            profiles::clone_profile,
+            profiles::rename_profile,
+            profiles::delete_profile,
+            profiles::switch_profile,
+            profiles::set_profile_hotkey,
+            transfer::export_settings,
+            transfer::import_settings
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let RunEvent::ExitRequested { .. } | RunEvent::Exit = event {
+                shutdown(app_handle);
+            }
+        });
 }
@@ -0,0 +1,105 @@
+use log::LevelFilter;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use tauri::plugin::TauriPlugin;
+use tauri::{Manager, Runtime};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind, TimezoneStrategy};
+
+/// Log targets, so a report can be filtered down to one part of the app.
+pub mod target {
+    /// The click thread and the clicker state machine.
+    pub const CLICKER: &str = "clicker";
+    /// Hotkey polling, profile hotkeys and the safety checks run alongside.
+    pub const HOTKEYS: &str = "hotkeys";
+    /// Reading, migrating and writing the stores.
+    pub const STORE: &str = "store";
+    /// Everything else: history, events, startup.
+    pub const APP: &str = "app";
+}
+
+/// `autoclicker.log` in the app log dir.
+const FILE_NAME: &str = "autoclicker";
+const MAX_FILE_BYTES: u128 = 1024 * 1024;
+/// Rotated files kept besides the current one.
+const ROTATED_FILES: usize = 4;
+const DEFAULT_RECENT_LINES: usize = 200;
+
+/// Logs to stderr, keeping stdout for CLI output, and to a rotating file in the
+/// app log dir. Debug builds also keep debug-level records.
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    let level = if cfg!(debug_assertions) {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Info
+    };
+    tauri_plugin_log::Builder::new()
+        .clear_targets()
+        .targets([
+            Target::new(TargetKind::Stderr),
+            Target::new(TargetKind::LogDir {
+                file_name: Some(FILE_NAME.to_string()),
+            }),
+        ])
+        .level(level)
+        .max_file_size(MAX_FILE_BYTES)
+        .rotation_strategy(RotationStrategy::KeepSome(ROTATED_FILES))
+        .timezone_strategy(TimezoneStrategy::UseLocal)
+        .build()
+}
+
+#[derive(Debug)]
+pub enum LogError {
+    NoLogDir(tauri::Error),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogError::NoLogDir(e) => write!(f, "the log directory is unavailable: {}", e),
+            LogError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for LogError {
+    fn from(e: std::io::Error) -> Self {
+        LogError::Io(e)
+    }
+}
+
+impl Serialize for LogError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+pub fn log_file(app_handle: &tauri::AppHandle) -> Result<PathBuf, LogError> {
+    let dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(LogError::NoLogDir)?;
+    Ok(dir.join(FILE_NAME).with_extension("log"))
+}
+
+/// The last `count` lines of the current log file, oldest first.
+pub fn recent_lines(app_handle: &tauri::AppHandle, count: usize) -> Result<Vec<String>, LogError> {
+    let contents = match fs::read_to_string(log_file(app_handle)?) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+    let start = lines.len().saturating_sub(count);
+    Ok(lines[start..].iter().map(|line| line.to_string()).collect())
+}
+
+#[tauri::command]
+pub fn get_recent_logs(
+    app_handle: tauri::AppHandle,
+    lines: Option<usize>,
+) -> Result<Vec<String>, LogError> {
+    recent_lines(&app_handle, lines.unwrap_or(DEFAULT_RECENT_LINES))
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+use ts_rs::TS;
+
+use crate::backend::{BackendStatus, InputBackend};
+use crate::clicker_state::StopReason;
+use crate::logging::target;
+use crate::settings::SharedSettings;
+use crate::stats::StatsTracker;
+
+/// Which stops raise a desktop notification. Stops the user asked for never do.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    /// The fail-safe, the panic hotkey and taking over the mouse.
+    pub safety_stops: bool,
+    /// Input backend failures and crashed worker threads.
+    pub errors: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            safety_stops: true,
+            errors: true,
+        }
+    }
+}
+
+impl NotificationSettings {
+    fn wants(&self, reason: StopReason) -> bool {
+        self.enabled
+            && match reason {
+                StopReason::User | StopReason::AppExit => false,
+                StopReason::EmergencyStop | StopReason::Failsafe | StopReason::UserTookOver => {
+                    self.safety_stops
+                }
+                StopReason::BackendError | StopReason::WorkerCrashed => self.errors,
+            }
+    }
+}
+
+fn message(app_handle: &tauri::AppHandle, reason: StopReason) -> (&'static str, String) {
+    match reason {
+        StopReason::EmergencyStop => (
+            "Emergency stop",
+            "The panic hotkey stopped the clicker.".to_string(),
+        ),
+        StopReason::Failsafe => (
+            "Fail-safe triggered",
+            "The cursor reached the fail-safe corner.".to_string(),
+        ),
+        StopReason::UserTookOver => (
+            "Clicking paused",
+            "You moved the mouse, so the active channels stopped.".to_string(),
+        ),
+        StopReason::BackendError => {
+            let detail = match app_handle.state::<InputBackend>().status() {
+                BackendStatus::Failed { error, .. } => format!("Clicks can't be sent: {}.", error),
+                _ => "Clicks can't be sent.".to_string(),
+            };
+            ("Clicking stopped", detail)
+        }
+        StopReason::WorkerCrashed => (
+            "Clicking stopped",
+            "A background thread crashed and was restarted.".to_string(),
+        ),
+        StopReason::User | StopReason::AppExit => ("Clicking stopped", String::new()),
+    }
+}
+
+/// Tells the user why clicking stopped, if they want to hear about `reason`.
+/// Shown from a background task, so it never blocks the caller.
+pub fn notify_stop(app_handle: &tauri::AppHandle, reason: StopReason) {
+    let settings = app_handle
+        .state::<SharedSettings>()
+        .snapshot()
+        .notifications;
+    if !settings.wants(reason) {
+        return;
+    }
+    let clicks = app_handle
+        .state::<StatsTracker>()
+        .snapshot()
+        .session_clicks();
+    let (title, detail) = message(app_handle, reason);
+    let result = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(format!("{} {} clicks this session.", detail, clicks))
+        .show();
+    if let Err(e) = result {
+        log::warn!(target: target::APP, "Failed to show a notification: {}", e);
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use tauri::{
+    Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window, WindowEvent,
+};
+use ts_rs::TS;
+
+use crate::clicker_state::ClickerState;
+use crate::logging::target;
+use crate::settings::{SettingsError, SharedSettings};
+
+const OVERLAY_WINDOW: &str = "overlay";
+/// Logical size of the overlay window.
+const WIDTH: f64 = 200.0;
+const HEIGHT: f64 = 72.0;
+/// Logical gap between the overlay and the edges of the screen.
+const MARGIN: f64 = 16.0;
+/// Below this the overlay is too faint to read.
+const MIN_OPACITY: f64 = 0.2;
+
+/// The screen corner the overlay sits in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum OverlayPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// The on-screen indicator shown while the clicker runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OverlaySettings {
+    pub enabled: bool,
+    pub position: OverlayPosition,
+    /// From 0.2 to 1.
+    pub opacity: f64,
+}
+
+impl Default for OverlaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: OverlayPosition::default(),
+            opacity: 0.85,
+        }
+    }
+}
+
+impl OverlaySettings {
+    pub fn validate(&self, field: &'static str, errors: &mut Vec<SettingsError>) {
+        if !(MIN_OPACITY..=1.0).contains(&self.opacity) {
+            errors.push(SettingsError {
+                field,
+                message: format!("opacity must be {}-1, got {}", MIN_OPACITY, self.opacity),
+            });
+        }
+    }
+}
+
+/// Creates the hidden overlay window. It never takes focus and lets clicks
+/// through, so it can't get in the way of what's being clicked.
+fn create(app_handle: &tauri::AppHandle) -> tauri::Result<WebviewWindow> {
+    let window = WebviewWindowBuilder::new(
+        app_handle,
+        OVERLAY_WINDOW,
+        WebviewUrl::App("index.html".into()),
+    )
+    .title("autoclicker overlay")
+    .inner_size(WIDTH, HEIGHT)
+    .resizable(false)
+    .decorations(false)
+    .transparent(true)
+    .shadow(false)
+    .always_on_top(true)
+    .visible_on_all_workspaces(true)
+    .skip_taskbar(true)
+    .focused(false)
+    .focusable(false)
+    .visible(false)
+    .build()?;
+    window.set_ignore_cursor_events(true)?;
+    Ok(window)
+}
+
+/// Moves the overlay into `position` on the primary monitor's work area.
+fn place(window: &WebviewWindow, position: OverlayPosition) -> tauri::Result<()> {
+    let Some(monitor) = window.primary_monitor()? else {
+        return Ok(());
+    };
+    let area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let (width, height, margin) = (WIDTH * scale, HEIGHT * scale, MARGIN * scale);
+    let left = area.position.x as f64 + margin;
+    let top = area.position.y as f64 + margin;
+    let right = (area.position.x + area.size.width as i32) as f64 - width - margin;
+    let bottom = (area.position.y + area.size.height as i32) as f64 - height - margin;
+    let (x, y) = match position {
+        OverlayPosition::TopLeft => (left, top),
+        OverlayPosition::TopRight => (right, top),
+        OverlayPosition::BottomLeft => (left, bottom),
+        OverlayPosition::BottomRight => (right, bottom),
+    };
+    window.set_position(PhysicalPosition::new(x as i32, y as i32))
+}
+
+/// Shows the overlay while the clicker runs and it's enabled, hides it
+/// otherwise. Must run on the main thread; use `schedule_update` from anywhere
+/// else.
+pub fn update(app_handle: &tauri::AppHandle) {
+    let settings = app_handle.state::<SharedSettings>().snapshot().overlay;
+    let running = app_handle
+        .try_state::<ClickerState>()
+        .is_some_and(|state| state.is_running());
+    let window = app_handle.get_webview_window(OVERLAY_WINDOW);
+    if !(settings.enabled && running) {
+        if let Some(window) = window {
+            let _ = window.hide();
+        }
+        return;
+    }
+
+    let result = match window {
+        Some(window) => Ok(window),
+        None => create(app_handle),
+    }
+    .and_then(|window| {
+        place(&window, settings.position)?;
+        window.show()?;
+        // Some platforms forget this while the window is hidden.
+        window.set_ignore_cursor_events(true)
+    });
+    if let Err(e) = result {
+        log::warn!(target: target::APP, "Failed to show the overlay: {}", e);
+    }
+}
+
+/// Runs `update` on the main thread: queued from other threads, right away on
+/// the main thread itself. `update` reads the clicker state, so never call this
+/// with it locked.
+pub fn schedule_update(app_handle: &tauri::AppHandle) {
+    let handle = app_handle.clone();
+    if let Err(e) = app_handle.run_on_main_thread(move || update(&handle)) {
+        log::warn!(target: target::APP, "Failed to schedule an overlay update: {}", e);
+    }
+}
+
+/// Closes the overlay along with the last other window, which it would
+/// otherwise keep the app running for.
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    if window.label() == OVERLAY_WINDOW || !matches!(event, WindowEvent::Destroyed) {
+        return;
+    }
+    let app_handle = window.app_handle();
+    let others_open = app_handle
+        .webview_windows()
+        .keys()
+        .any(|label| label != OVERLAY_WINDOW && label != window.label());
+    if let Some(overlay) = app_handle.get_webview_window(OVERLAY_WINDOW) {
+        if !others_open {
+            let _ = overlay.destroy();
+        }
+    }
+}
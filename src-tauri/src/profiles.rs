@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use tauri::Emitter;
+use ts_rs::TS;
+
+use crate::hotkey_validation::{self, IssueSeverity};
+use crate::logging::target;
+use crate::settings::{self, AutoclickerSettings, ChannelTiming};
+use crate::store_keys::autoclicker_keys;
+
+pub const DEFAULT_PROFILE: &str = "Default";
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+/// The per-profile part of the settings. The active profile lives in the flat
+/// keys of the store, which the UI edits directly; its entry in `profiles` is
+/// refreshed whenever the active profile changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Profile {
+    pub left_channel: ChannelTiming,
+    pub right_channel: ChannelTiming,
+    pub hold_mode: bool,
+    pub hotkey_left: String,
+    pub hotkey_right: String,
+    /// Switches to this profile while the clicker is running, empty disables.
+    pub switch_hotkey: String,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::from_settings(&AutoclickerSettings::default(), String::new())
+    }
+}
+
+impl Profile {
+    pub fn from_settings(settings: &AutoclickerSettings, switch_hotkey: String) -> Self {
+        Self {
+            left_channel: settings.left_channel,
+            right_channel: settings.right_channel,
+            hold_mode: settings.hold_mode,
+            hotkey_left: settings.hotkey_left.clone(),
+            hotkey_right: settings.hotkey_right.clone(),
+            switch_hotkey,
+        }
+    }
+
+    fn apply_to(&self, settings: &mut AutoclickerSettings) {
+        settings.left_channel = self.left_channel;
+        settings.right_channel = self.right_channel;
+        settings.hold_mode = self.hold_mode;
+        settings.hotkey_left = self.hotkey_left.clone();
+        settings.hotkey_right = self.hotkey_right.clone();
+    }
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    NotFound(String),
+    AlreadyExists(String),
+    InvalidName(String),
+    LastProfile,
+    InvalidHotkey(Vec<String>),
+    Store(tauri_store::Error),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::NotFound(name) => write!(f, "profile \"{}\" does not exist", name),
+            ProfileError::AlreadyExists(name) => {
+                write!(f, "a profile named \"{}\" already exists", name)
+            }
+            ProfileError::InvalidName(name) => write!(
+                f,
+                "\"{}\" is not a valid profile name (1-{} characters)",
+                name, MAX_PROFILE_NAME_LEN
+            ),
+            ProfileError::LastProfile => write!(f, "the last profile cannot be deleted"),
+            ProfileError::InvalidHotkey(errors) => write!(f, "{}", errors.join(", ")),
+            ProfileError::Store(e) => write!(f, "failed to save profiles: {}", e),
+        }
+    }
+}
+
+impl From<tauri_store::Error> for ProfileError {
+    fn from(e: tauri_store::Error) -> Self {
+        ProfileError::Store(e)
+    }
+}
+
+impl Serialize for ProfileError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+pub fn validate_name(name: &str) -> Result<String, ProfileError> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(ProfileError::InvalidName(name.to_string()));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn switch_binding(name: &str) -> String {
+    format!("profile:{}", name)
+}
+
+/// Everything the listener matches while `active` is the active profile: its
+/// channel hotkeys, the panic hotkey and every profile's switch hotkey.
+fn bindings_while_active(settings: &AutoclickerSettings, active: &str) -> BTreeMap<String, String> {
+    // The active profile's entry may be stale; its live keys are the flat ones.
+    let (hotkey_left, hotkey_right) = match settings.profiles.get(active) {
+        Some(profile) if active != settings.active_profile => {
+            (&profile.hotkey_left, &profile.hotkey_right)
+        }
+        _ => (&settings.hotkey_left, &settings.hotkey_right),
+    };
+    let mut bindings = BTreeMap::from([
+        (
+            autoclicker_keys::HOTKEY_LEFT.to_string(),
+            hotkey_left.clone(),
+        ),
+        (
+            autoclicker_keys::HOTKEY_RIGHT.to_string(),
+            hotkey_right.clone(),
+        ),
+        (
+            autoclicker_keys::PANIC_HOTKEY.to_string(),
+            settings.panic_hotkey.clone(),
+        ),
+    ]);
+    for (name, profile) in &settings.profiles {
+        bindings.insert(switch_binding(name), profile.switch_hotkey.clone());
+    }
+    bindings
+}
+
+/// The hotkey errors of every profile, checked as they are live while that
+/// profile is active. Profiles may share channel hotkeys, since only one set is
+/// live at a time. With `concerning`, only errors involving that binding count.
+pub fn hotkey_errors(settings: &AutoclickerSettings, concerning: Option<&str>) -> Vec<String> {
+    let mut errors: Vec<String> = Vec::new();
+    for name in settings.profiles.keys() {
+        let issues = hotkey_validation::validate_bindings(&bindings_while_active(settings, name))
+            .issues
+            .into_iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .filter(|issue| {
+                concerning.is_none_or(|binding| issue.bindings.iter().any(|b| b == binding))
+            });
+        for issue in issues {
+            if !errors.contains(&issue.message) {
+                errors.push(issue.message);
+            }
+        }
+    }
+    errors
+}
+
+/// Checks `hotkey` as the switch hotkey of profile `name` against the other
+/// switch hotkeys and the channel and panic bindings of every profile, since it
+/// works in all of them. Problems elsewhere are not its concern.
+fn validate_switch_hotkey(
+    settings: &AutoclickerSettings,
+    name: &str,
+    hotkey: &str,
+) -> Result<(), ProfileError> {
+    let mut updated = settings.clone();
+    updated.profile_mut(name)?.switch_hotkey = hotkey.to_string();
+    let errors = hotkey_errors(&updated, Some(&switch_binding(name)));
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ProfileError::InvalidHotkey(errors))
+    }
+}
+
+impl AutoclickerSettings {
+    /// Writes the live values back into the active profile's entry.
+    pub fn store_active_profile(&mut self) {
+        let switch_hotkey = self
+            .profiles
+            .get(&self.active_profile)
+            .map(|p| p.switch_hotkey.clone())
+            .unwrap_or_default();
+        let profile = Profile::from_settings(self, switch_hotkey);
+        self.profiles.insert(self.active_profile.clone(), profile);
+    }
+
+    fn profile_mut(&mut self, name: &str) -> Result<&mut Profile, ProfileError> {
+        self.profiles
+            .get_mut(name)
+            .ok_or_else(|| ProfileError::NotFound(name.to_string()))
+    }
+
+    fn ensure_free(&self, name: &str) -> Result<(), ProfileError> {
+        if self.profiles.contains_key(name) {
+            return Err(ProfileError::AlreadyExists(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Loads a profile into the live values without saving the current ones first.
+    pub fn activate_profile(&mut self, name: &str) -> Result<(), ProfileError> {
+        let profile = self
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ProfileError::NotFound(name.to_string()))?;
+        profile.apply_to(self);
+        self.active_profile = name.to_string();
+        Ok(())
+    }
+
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), ProfileError> {
+        if !self.profiles.contains_key(name) {
+            return Err(ProfileError::NotFound(name.to_string()));
+        }
+        self.store_active_profile();
+        self.activate_profile(name)
+    }
+}
+
+#[tauri::command]
+pub fn create_profile(app_handle: tauri::AppHandle, name: String) -> Result<(), ProfileError> {
+    let name = validate_name(&name)?;
+    settings::update(&app_handle, |settings| {
+        settings.ensure_free(&name)?;
+        settings.profiles.insert(name, Profile::default());
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn clone_profile(
+    app_handle: tauri::AppHandle,
+    source: String,
+    name: String,
+) -> Result<(), ProfileError> {
+    let name = validate_name(&name)?;
+    settings::update(&app_handle, |settings| {
+        settings.ensure_free(&name)?;
+        settings.store_active_profile();
+        let mut profile = settings.profile_mut(&source)?.clone();
+        // Two profiles can't share a switch hotkey.
+        profile.switch_hotkey = String::new();
+        settings.profiles.insert(name, profile);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn rename_profile(
+    app_handle: tauri::AppHandle,
+    name: String,
+    new_name: String,
+) -> Result<(), ProfileError> {
+    let new_name = validate_name(&new_name)?;
+    settings::update(&app_handle, |settings| {
+        let Some(profile) = settings.profiles.get(&name).cloned() else {
+            return Err(ProfileError::NotFound(name));
+        };
+        if name == new_name {
+            return Ok(());
+        }
+        settings.ensure_free(&new_name)?;
+        settings.profiles.remove(&name);
+        settings.profiles.insert(new_name.clone(), profile);
+        if settings.active_profile == name {
+            settings.active_profile = new_name;
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn delete_profile(app_handle: tauri::AppHandle, name: String) -> Result<(), ProfileError> {
+    settings::update(&app_handle, |settings| {
+        if !settings.profiles.contains_key(&name) {
+            return Err(ProfileError::NotFound(name));
+        }
+        if settings.profiles.len() == 1 {
+            return Err(ProfileError::LastProfile);
+        }
+        if settings.active_profile == name {
+            let fallback = settings
+                .profiles
+                .keys()
+                .find(|other| **other != name)
+                .cloned()
+                .unwrap_or_default();
+            settings.switch_profile(&fallback)?;
+        }
+        settings.profiles.remove(&name);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn switch_profile(app_handle: tauri::AppHandle, name: String) -> Result<(), ProfileError> {
+    settings::update(&app_handle, |settings| settings.switch_profile(&name))?;
+    app_handle.emit("profile-switched", &name).unwrap_or_else(
+        |e| log::warn!(target: target::APP, "Failed to emit profile-switched: {}", e),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_profile_hotkey(
+    app_handle: tauri::AppHandle,
+    name: String,
+    hotkey: String,
+) -> Result<(), ProfileError> {
+    settings::update(&app_handle, |settings| {
+        validate_switch_hotkey(settings, &name, &hotkey)?;
+        settings.profile_mut(&name)?.switch_hotkey = hotkey;
+        Ok(())
+    })
+}
@@ -0,0 +1,514 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, PoisonError, RwLock};
+use std::time::Duration;
+use tauri::Manager;
+use tauri_store::{ManagerExt, StoreState};
+use ts_rs::TS;
+
+use crate::audio::SoundSettings;
+use crate::failsafe::FailsafeCorner;
+use crate::hotkey_utils;
+use crate::logging::target;
+use crate::notifications::NotificationSettings;
+use crate::overlay::{self, OverlaySettings};
+use crate::profiles::{Profile, DEFAULT_PROFILE};
+use crate::store_keys::{autoclicker_keys, store};
+use crate::supervisor::MutexExt;
+use crate::tray;
+
+/// Version of the `autoclicker` store layout. Bump it together with a new entry
+/// in `MIGRATIONS`.
+pub const SCHEMA_VERSION: u32 = 3;
+
+pub const DEFAULT_PANIC_HOTKEY: &str = "Ctrl+Alt+Shift+Escape";
+
+/// How fast one click channel clicks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChannelTiming {
+    /// Milliseconds between clicks.
+    pub click_speed: f64,
+    /// How far each interval may stray from `click_speed`, in percent.
+    pub randomization: f64,
+}
+
+impl Default for ChannelTiming {
+    fn default() -> Self {
+        Self {
+            click_speed: 100.0,
+            randomization: 0.0,
+        }
+    }
+}
+
+impl ChannelTiming {
+    /// The wait before the next click, randomized within `randomization`.
+    pub fn next_interval(&self) -> Duration {
+        let jitter = if self.randomization > 0.0 {
+            rand::random_range(-1.0..=1.0) * self.randomization / 100.0
+        } else {
+            0.0
+        };
+        Duration::from_secs_f64((self.click_speed * (1.0 + jitter)).max(0.0) / 1000.0)
+    }
+
+    fn validate(&self, field: &'static str, errors: &mut Vec<SettingsError>) {
+        if !self.click_speed.is_finite() || self.click_speed <= 0.0 {
+            errors.push(SettingsError {
+                field,
+                message: format!(
+                    "click speed must be a positive number, got {}",
+                    self.click_speed
+                ),
+            });
+        }
+        if !(0.0..=100.0).contains(&self.randomization) {
+            errors.push(SettingsError {
+                field,
+                message: format!("randomization must be 0-100%, got {}", self.randomization),
+            });
+        }
+    }
+}
+
+/// Everything persisted in the `autoclicker` store. Each field is stored under its
+/// own key so the frontend can keep reading and writing them individually.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoclickerSettings {
+    pub schema_version: u32,
+    pub left_channel: ChannelTiming,
+    pub right_channel: ChannelTiming,
+    pub hold_mode: bool,
+    pub hotkey_left: String,
+    pub hotkey_right: String,
+    pub panic_hotkey: String,
+    pub failsafe_corner: FailsafeCorner,
+    /// Pixels the cursor may move away from where it was at the last click
+    /// before active channels stop, in both modes. 0 disables.
+    pub mouse_move_threshold: u32,
+    /// Start listening again on launch if the clicker was running when the app
+    /// last exited. Channels always start off.
+    pub resume_previous_session: bool,
+    /// Closing or minimizing the window hides it to the tray instead.
+    pub minimize_to_tray: bool,
+    /// Launch with only the tray icon showing.
+    pub start_hidden: bool,
+    pub overlay: OverlaySettings,
+    pub sounds: SoundSettings,
+    pub notifications: NotificationSettings,
+    pub profiles: BTreeMap<String, Profile>,
+    pub active_profile: String,
+}
+
+impl Default for AutoclickerSettings {
+    fn default() -> Self {
+        let mut settings = Self {
+            schema_version: SCHEMA_VERSION,
+            left_channel: ChannelTiming::default(),
+            right_channel: ChannelTiming::default(),
+            hold_mode: false,
+            hotkey_left: "F5".to_string(),
+            hotkey_right: "F6".to_string(),
+            panic_hotkey: DEFAULT_PANIC_HOTKEY.to_string(),
+            failsafe_corner: FailsafeCorner::default(),
+            mouse_move_threshold: 0,
+            resume_previous_session: false,
+            minimize_to_tray: false,
+            start_hidden: false,
+            overlay: OverlaySettings::default(),
+            sounds: SoundSettings::default(),
+            notifications: NotificationSettings::default(),
+            profiles: BTreeMap::new(),
+            active_profile: DEFAULT_PROFILE.to_string(),
+        };
+        settings.store_active_profile();
+        settings
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl AutoclickerSettings {
+    /// Reads the settings key by key, so a single malformed value falls back to
+    /// its default instead of discarding the whole store.
+    pub fn from_state(state: &StoreState) -> Self {
+        let defaults = Self::default();
+        Self {
+            schema_version: state.get_or(autoclicker_keys::SCHEMA_VERSION, 0),
+            left_channel: state.get_or(autoclicker_keys::LEFT_CHANNEL, defaults.left_channel),
+            right_channel: state.get_or(autoclicker_keys::RIGHT_CHANNEL, defaults.right_channel),
+            hold_mode: state.get_or(autoclicker_keys::HOLD_MODE, defaults.hold_mode),
+            hotkey_left: state.get_or(autoclicker_keys::HOTKEY_LEFT, defaults.hotkey_left),
+            hotkey_right: state.get_or(autoclicker_keys::HOTKEY_RIGHT, defaults.hotkey_right),
+            panic_hotkey: state.get_or(autoclicker_keys::PANIC_HOTKEY, defaults.panic_hotkey),
+            failsafe_corner: state
+                .get_or(autoclicker_keys::FAILSAFE_CORNER, defaults.failsafe_corner),
+            mouse_move_threshold: state.get_or(
+                autoclicker_keys::MOUSE_MOVE_THRESHOLD,
+                defaults.mouse_move_threshold,
+            ),
+            resume_previous_session: state.get_or(
+                autoclicker_keys::RESUME_PREVIOUS_SESSION,
+                defaults.resume_previous_session,
+            ),
+            minimize_to_tray: state.get_or(
+                autoclicker_keys::MINIMIZE_TO_TRAY,
+                defaults.minimize_to_tray,
+            ),
+            start_hidden: state.get_or(autoclicker_keys::START_HIDDEN, defaults.start_hidden),
+            overlay: state.get_or(autoclicker_keys::OVERLAY, defaults.overlay),
+            sounds: state.get_or(autoclicker_keys::SOUNDS, defaults.sounds),
+            notifications: state.get_or(autoclicker_keys::NOTIFICATIONS, defaults.notifications),
+            profiles: state.get_or(autoclicker_keys::PROFILES, defaults.profiles),
+            active_profile: state.get_or(autoclicker_keys::ACTIVE_PROFILE, defaults.active_profile),
+        }
+    }
+
+    pub fn to_state(&self) -> StoreState {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().collect(),
+            _ => StoreState::new(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<SettingsError>> {
+        let mut errors = Vec::new();
+        self.left_channel
+            .validate(autoclicker_keys::LEFT_CHANNEL, &mut errors);
+        self.right_channel
+            .validate(autoclicker_keys::RIGHT_CHANNEL, &mut errors);
+        self.overlay
+            .validate(autoclicker_keys::OVERLAY, &mut errors);
+        self.sounds.validate(autoclicker_keys::SOUNDS, &mut errors);
+        if !self.profiles.contains_key(&self.active_profile) {
+            errors.push(SettingsError {
+                field: autoclicker_keys::ACTIVE_PROFILE,
+                message: format!("profile \"{}\" does not exist", self.active_profile),
+            });
+        }
+        if self.schema_version > SCHEMA_VERSION {
+            errors.push(SettingsError {
+                field: autoclicker_keys::SCHEMA_VERSION,
+                message: format!(
+                    "{} is newer than the supported version {}",
+                    self.schema_version, SCHEMA_VERSION
+                ),
+            });
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Replaces every invalid field with its default.
+    pub fn sanitized(mut self) -> Self {
+        if let Err(errors) = self.validate() {
+            let defaults = Self::default();
+            for error in errors {
+                log::warn!(target: target::STORE, "Invalid setting {}, using default", error);
+                match error.field {
+                    autoclicker_keys::LEFT_CHANNEL => self.left_channel = defaults.left_channel,
+                    autoclicker_keys::RIGHT_CHANNEL => self.right_channel = defaults.right_channel,
+                    autoclicker_keys::OVERLAY => self.overlay = defaults.overlay,
+                    autoclicker_keys::SOUNDS => self.sounds = defaults.sounds.clone(),
+                    autoclicker_keys::SCHEMA_VERSION => {
+                        self.schema_version = defaults.schema_version
+                    }
+                    // Adopt the live values as a profile rather than switching to
+                    // another one, so nothing the user sees changes.
+                    autoclicker_keys::ACTIVE_PROFILE => {
+                        if self.active_profile.trim().is_empty() {
+                            self.active_profile = defaults.active_profile.clone();
+                        }
+                        self.store_active_profile();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self
+    }
+}
+
+type Migration = fn(&mut StoreState);
+
+/// `MIGRATIONS[n]` upgrades a store from schema version `n` to `n + 1`.
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// Version 0 is the unversioned layout, where hotkeys were whatever the browser
+/// reported for the key (`a`, `ArrowUp`, `Control+...`). Rewrite them into the
+/// canonical form the listener records now; unparseable ones are left for the
+/// hotkey validation to report.
+fn migrate_v0_to_v1(state: &mut StoreState) {
+    for key in [
+        autoclicker_keys::HOTKEY_LEFT,
+        autoclicker_keys::HOTKEY_RIGHT,
+    ] {
+        let Ok(hotkey) = state.get::<String>(key) else {
+            continue;
+        };
+        if let Ok(parsed) = hotkey_utils::parse_hotkey(&hotkey) {
+            state.set(key, parsed.to_string());
+        }
+    }
+    if let Ok(speed) = state.get::<String>(autoclicker_keys::CLICK_SPEED) {
+        if let Ok(speed) = speed.trim().parse::<f64>() {
+            state.set(autoclicker_keys::CLICK_SPEED, speed);
+        }
+    }
+}
+
+/// Version 2 introduced profiles; the existing setup becomes the default profile.
+/// Works on the raw values, since later versions changed what a profile holds.
+fn migrate_v1_to_v2(state: &mut StoreState) {
+    let mut profile = serde_json::Map::new();
+    for key in [
+        autoclicker_keys::CLICK_SPEED,
+        autoclicker_keys::HOLD_MODE,
+        autoclicker_keys::HOTKEY_LEFT,
+        autoclicker_keys::HOTKEY_RIGHT,
+    ] {
+        if let Ok(value) = state.get::<Value>(key) {
+            profile.insert(key.to_string(), value);
+        }
+    }
+    let profiles = serde_json::json!({ DEFAULT_PROFILE: profile });
+    state.set(autoclicker_keys::PROFILES, profiles);
+    state.set(autoclicker_keys::ACTIVE_PROFILE, DEFAULT_PROFILE);
+}
+
+/// Replaces a pre-version-3 `clickSpeed` with the same timing on both channels.
+/// Also used for profiles in files exported before then.
+pub fn split_click_speed(values: &mut serde_json::Map<String, Value>) {
+    let Some(speed) = values.remove(autoclicker_keys::CLICK_SPEED) else {
+        return;
+    };
+    let timing = ChannelTiming {
+        click_speed: speed
+            .as_f64()
+            .unwrap_or(ChannelTiming::default().click_speed),
+        ..Default::default()
+    };
+    if let Ok(timing) = serde_json::to_value(timing) {
+        values.insert(autoclicker_keys::LEFT_CHANNEL.to_string(), timing.clone());
+        values.insert(autoclicker_keys::RIGHT_CHANNEL.to_string(), timing);
+    }
+}
+
+/// Version 3 split the shared click speed into one timing per channel.
+fn migrate_v2_to_v3(state: &mut StoreState) {
+    let mut flat = serde_json::Map::new();
+    if let Some(speed) = state.remove(autoclicker_keys::CLICK_SPEED) {
+        flat.insert(autoclicker_keys::CLICK_SPEED.to_string(), speed);
+    }
+    split_click_speed(&mut flat);
+    for (key, value) in flat {
+        state.set(key, value);
+    }
+
+    if let Some(Value::Object(profiles)) = state.get_raw_mut(autoclicker_keys::PROFILES) {
+        for profile in profiles.values_mut() {
+            if let Value::Object(profile) = profile {
+                split_click_speed(profile);
+            }
+        }
+    }
+}
+
+/// Brings a raw store state up to `SCHEMA_VERSION`. Returns whether anything ran.
+pub fn migrate(state: &mut StoreState) -> bool {
+    let from: u32 = state.get_or(autoclicker_keys::SCHEMA_VERSION, 0);
+    if from >= SCHEMA_VERSION {
+        return false;
+    }
+    for migration in &MIGRATIONS[from as usize..] {
+        migration(state);
+    }
+    state.set(autoclicker_keys::SCHEMA_VERSION, SCHEMA_VERSION);
+    true
+}
+
+/// Migrates and validates the persisted settings, writing the result back so the
+/// store and the frontend see the same values the backend uses.
+pub fn load(app_handle: &tauri::AppHandle) -> AutoclickerSettings {
+    let mut state = app_handle
+        .store_collection()
+        .raw_state(store::AUTOCLICKER)
+        .unwrap_or_default();
+    let migrated = migrate(&mut state);
+    let loaded = AutoclickerSettings::from_state(&state);
+    if loaded.schema_version > SCHEMA_VERSION {
+        // Written by a newer build; use what we understand but don't overwrite it.
+        return loaded.sanitized();
+    }
+    let settings = loaded.clone().sanitized();
+
+    if migrated || settings != loaded {
+        let collection = app_handle.store_collection();
+        if let Err(e) = collection
+            .patch(store::AUTOCLICKER, settings.to_state())
+            .and_then(|_| collection.save(store::AUTOCLICKER))
+        {
+            log::error!(target: target::STORE, "Failed to write migrated settings to store: {}", e);
+        }
+    }
+    settings
+}
+
+/// The settings every thread reads from. A single store watcher keeps it current,
+/// so all readers see one consistent snapshot instead of separate copies per key.
+pub struct SharedSettings {
+    current: RwLock<AutoclickerSettings>,
+    update_lock: Mutex<()>,
+}
+
+impl SharedSettings {
+    pub fn snapshot(&self) -> AutoclickerSettings {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn replace(&self, settings: AutoclickerSettings) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = settings;
+    }
+}
+
+/// Applies `f` to the current settings and persists the result if it succeeds.
+/// Updates are serialized so concurrent commands can't overwrite each other.
+pub fn update<T, E>(
+    app_handle: &tauri::AppHandle,
+    f: impl FnOnce(&mut AutoclickerSettings) -> Result<T, E>,
+) -> Result<T, E>
+where
+    E: From<tauri_store::Error>,
+{
+    let shared = app_handle.state::<SharedSettings>();
+    let _guard = shared.update_lock.lock_unpoisoned();
+    let collection = app_handle.store_collection();
+
+    // Read the store rather than the snapshot so edits the UI just made are kept.
+    let state = collection.raw_state(store::AUTOCLICKER)?;
+    let mut settings = AutoclickerSettings::from_state(&state).sanitized();
+    let result = f(&mut settings)?;
+
+    collection.patch(store::AUTOCLICKER, settings.to_state())?;
+    collection.save(store::AUTOCLICKER)?;
+    shared.replace(settings);
+    tray::schedule_refresh(app_handle);
+    overlay::schedule_update(app_handle);
+    Ok(result)
+}
+
+pub fn init(app_handle: &tauri::AppHandle) {
+    app_handle.manage(SharedSettings {
+        current: RwLock::new(load(app_handle)),
+        update_lock: Mutex::new(()),
+    });
+
+    let _ = app_handle
+        .store_collection()
+        .watch(store::AUTOCLICKER, move |app| {
+            reload(&app);
+            Ok(())
+        });
+}
+
+/// Replaces the shared snapshot with what's in the store.
+pub fn reload(app_handle: &tauri::AppHandle) {
+    if let Ok(state) = app_handle.store_collection().raw_state(store::AUTOCLICKER) {
+        let settings = AutoclickerSettings::from_state(&state).sanitized();
+        app_handle.state::<SharedSettings>().replace(settings);
+        tray::schedule_refresh(app_handle);
+        overlay::schedule_update(app_handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrates_an_unversioned_store() {
+        let mut state = StoreState::from([
+            (autoclicker_keys::CLICK_SPEED, json!("50")),
+            (autoclicker_keys::HOLD_MODE, json!(true)),
+            (autoclicker_keys::HOTKEY_LEFT, json!("Control+a")),
+            (autoclicker_keys::HOTKEY_RIGHT, json!("ArrowUp")),
+        ]);
+        assert!(migrate(&mut state));
+        assert!(!state.has(autoclicker_keys::CLICK_SPEED));
+
+        let settings = AutoclickerSettings::from_state(&state);
+        assert_eq!(settings.schema_version, SCHEMA_VERSION);
+        assert_eq!(settings.hotkey_left, "Ctrl+A");
+        assert_eq!(settings.hotkey_right, "Up");
+        assert_eq!(settings.left_channel.click_speed, 50.0);
+        assert_eq!(settings.right_channel.click_speed, 50.0);
+
+        assert_eq!(settings.active_profile, DEFAULT_PROFILE);
+        let profile = &settings.profiles[DEFAULT_PROFILE];
+        assert!(profile.hold_mode);
+        assert_eq!(profile.hotkey_left, "Ctrl+A");
+        assert_eq!(profile.hotkey_right, "Up");
+        assert_eq!(profile.left_channel.click_speed, 50.0);
+        assert_eq!(profile.right_channel.click_speed, 50.0);
+    }
+
+    #[test]
+    fn splits_the_click_speed_of_v2_profiles() {
+        let mut state = StoreState::from([
+            (autoclicker_keys::SCHEMA_VERSION, json!(2)),
+            (autoclicker_keys::CLICK_SPEED, json!(20.0)),
+            (autoclicker_keys::ACTIVE_PROFILE, json!("Fast")),
+            (
+                autoclicker_keys::PROFILES,
+                json!({
+                    "Fast": { "clickSpeed": 20.0, "hotkeyLeft": "F6", "switchHotkey": "F1" },
+                    "Slow": { "clickSpeed": 500.0, "hotkeyLeft": "F6", "switchHotkey": "F2" },
+                }),
+            ),
+        ]);
+        assert!(migrate(&mut state));
+
+        let profiles = state.get::<Value>(autoclicker_keys::PROFILES).unwrap();
+        assert!(profiles["Slow"]
+            .get(autoclicker_keys::CLICK_SPEED)
+            .is_none());
+
+        let settings = AutoclickerSettings::from_state(&state);
+        assert_eq!(settings.left_channel.click_speed, 20.0);
+        assert_eq!(settings.right_channel.click_speed, 20.0);
+        let slow = &settings.profiles["Slow"];
+        assert_eq!(slow.left_channel.click_speed, 500.0);
+        assert_eq!(slow.right_channel.click_speed, 500.0);
+        assert_eq!(slow.switch_hotkey, "F2");
+        assert_eq!(settings.profiles["Fast"].left_channel.click_speed, 20.0);
+    }
+
+    #[test]
+    fn leaves_a_current_store_alone() {
+        let mut state = AutoclickerSettings::default().to_state();
+        state.set(autoclicker_keys::SCHEMA_VERSION, SCHEMA_VERSION);
+        assert!(!migrate(&mut state));
+    }
+}
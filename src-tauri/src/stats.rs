@@ -0,0 +1,186 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+use ts_rs::TS;
+
+use crate::clicker::Channel;
+use crate::logging::target;
+use crate::supervisor::MutexExt;
+
+/// How far back measured CPS and the interval figures look.
+const WINDOW: Duration = Duration::from_secs(2);
+/// Caps the window at very high click rates.
+const MAX_SAMPLES: usize = 2048;
+/// At most one `clicker-stats` event per this interval while clicking.
+const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelStats {
+    /// Clicks since the app started.
+    #[ts(type = "number")]
+    pub total_clicks: u64,
+    /// Clicks since the clicker was last started.
+    #[ts(type = "number")]
+    pub session_clicks: u64,
+    /// Clicks per second actually performed over the last couple of seconds.
+    pub measured_cps: f64,
+    pub min_interval_ms: Option<f64>,
+    pub max_interval_ms: Option<f64>,
+    pub avg_interval_ms: Option<f64>,
+    /// Clicks this session that came so late the next one was already due.
+    #[ts(type = "number")]
+    pub missed_deadlines: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickStats {
+    pub left: ChannelStats,
+    pub right: ChannelStats,
+}
+
+impl ClickStats {
+    pub fn channel(&self, channel: Channel) -> &ChannelStats {
+        match channel {
+            Channel::Left => &self.left,
+            Channel::Right => &self.right,
+        }
+    }
+
+    /// Clicks on both channels since the clicker was last started.
+    pub fn session_clicks(&self) -> u64 {
+        self.left.session_clicks + self.right.session_clicks
+    }
+}
+
+#[derive(Default)]
+struct ChannelTracker {
+    total_clicks: u64,
+    session_clicks: u64,
+    missed_deadlines: u64,
+    samples: VecDeque<Instant>,
+}
+
+impl ChannelTracker {
+    fn record(&mut self, at: Instant, missed: bool) {
+        self.total_clicks += 1;
+        self.session_clicks += 1;
+        if missed {
+            self.missed_deadlines += 1;
+        }
+        self.samples.push_back(at);
+        if self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+    }
+
+    fn snapshot(&mut self, now: Instant) -> ChannelStats {
+        while self
+            .samples
+            .front()
+            .is_some_and(|sample| now.duration_since(*sample) > WINDOW)
+        {
+            self.samples.pop_front();
+        }
+
+        let intervals: Vec<f64> = self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .map(|(a, b)| b.duration_since(*a).as_secs_f64() * 1000.0)
+            .collect();
+        let avg_interval_ms =
+            (!intervals.is_empty()).then(|| intervals.iter().sum::<f64>() / intervals.len() as f64);
+
+        ChannelStats {
+            total_clicks: self.total_clicks,
+            session_clicks: self.session_clicks,
+            measured_cps: avg_interval_ms.map_or(0.0, |avg| 1000.0 / avg),
+            min_interval_ms: intervals.iter().copied().reduce(f64::min),
+            max_interval_ms: intervals.iter().copied().reduce(f64::max),
+            avg_interval_ms,
+            missed_deadlines: self.missed_deadlines,
+        }
+    }
+}
+
+/// Click counters and timing for both channels, fed by the click thread.
+pub struct StatsTracker {
+    channels: Mutex<[ChannelTracker; 2]>,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+impl Default for StatsTracker {
+    fn default() -> Self {
+        Self {
+            channels: Mutex::new(Default::default()),
+            last_emit: Mutex::new(None),
+        }
+    }
+}
+
+fn index(channel: Channel) -> usize {
+    match channel {
+        Channel::Left => 0,
+        Channel::Right => 1,
+    }
+}
+
+impl StatsTracker {
+    pub fn record(&self, channel: Channel, at: Instant, missed: bool) {
+        self.channels.lock_unpoisoned()[index(channel)].record(at, missed);
+    }
+
+    pub fn start_session(&self) {
+        for tracker in self.channels.lock_unpoisoned().iter_mut() {
+            tracker.session_clicks = 0;
+            tracker.missed_deadlines = 0;
+            tracker.samples.clear();
+        }
+    }
+
+    /// Drops the channel's timing window so its measured CPS drops to zero
+    /// straight away instead of fading out.
+    pub fn channel_stopped(&self, channel: Channel) {
+        self.channels.lock_unpoisoned()[index(channel)]
+            .samples
+            .clear();
+    }
+
+    pub fn snapshot(&self) -> ClickStats {
+        let now = Instant::now();
+        let mut channels = self.channels.lock_unpoisoned();
+        ClickStats {
+            left: channels[index(Channel::Left)].snapshot(now),
+            right: channels[index(Channel::Right)].snapshot(now),
+        }
+    }
+
+    pub fn emit(&self, app_handle: &tauri::AppHandle) {
+        *self.last_emit.lock_unpoisoned() = Some(Instant::now());
+        app_handle
+            .emit("clicker-stats", self.snapshot())
+            .unwrap_or_else(
+                |e| log::warn!(target: target::APP, "Failed to emit clicker-stats: {}", e),
+            );
+    }
+
+    /// Emits unless an event already went out within `EMIT_INTERVAL`.
+    pub fn emit_throttled(&self, app_handle: &tauri::AppHandle) {
+        let recent = self
+            .last_emit
+            .lock_unpoisoned()
+            .is_some_and(|last| last.elapsed() < EMIT_INTERVAL);
+        if !recent {
+            self.emit(app_handle);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_stats(stats: tauri::State<StatsTracker>) -> ClickStats {
+    stats.snapshot()
+}
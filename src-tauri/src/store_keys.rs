@@ -1,18 +1,45 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
 pub mod store {
     pub const TEMP: &str = "temp";
     pub const AUTOCLICKER: &str = "autoclicker";
 }
 
 pub mod temp_keys {
-    pub const IS_RUNNING: &str = "isRunning";
-    pub const HOTKEY_LEFT_ACTIVE: &str = "hotkeyLeftActive";
-    pub const HOTKEY_RIGHT_ACTIVE: &str = "hotkeyRightActive";
+    /// Whether the clicker was running when the app last quit cleanly. Not part
+    /// of `TempState`, since it has to outlive the session that wrote it.
+    pub const RUNNING_AT_EXIT: &str = "runningAtExit";
 }
 
 pub mod autoclicker_keys {
+    pub const SCHEMA_VERSION: &str = "schemaVersion";
     pub const HOTKEY_LEFT: &str = "hotkeyLeft";
     pub const HOTKEY_RIGHT: &str = "hotkeyRight";
     pub const HOLD_MODE: &str = "holdMode";
+    /// Shared by both channels before schema version 3; only read by migrations.
     pub const CLICK_SPEED: &str = "clickSpeed";
+    pub const LEFT_CHANNEL: &str = "leftChannel";
+    pub const RIGHT_CHANNEL: &str = "rightChannel";
+    pub const PANIC_HOTKEY: &str = "panicHotkey";
+    pub const FAILSAFE_CORNER: &str = "failsafeCorner";
+    pub const MOUSE_MOVE_THRESHOLD: &str = "mouseMoveThreshold";
+    pub const RESUME_PREVIOUS_SESSION: &str = "resumePreviousSession";
+    pub const MINIMIZE_TO_TRAY: &str = "minimizeToTray";
+    pub const START_HIDDEN: &str = "startHidden";
+    pub const OVERLAY: &str = "overlay";
+    pub const SOUNDS: &str = "sounds";
+    pub const NOTIFICATIONS: &str = "notifications";
+    pub const PROFILES: &str = "profiles";
+    pub const ACTIVE_PROFILE: &str = "activeProfile";
 }
 
+/// The `temp` store: runtime flags shared with the frontend, never meant to
+/// outlive the session. Reset on every launch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TempState {
+    pub is_running: bool,
+    pub hotkey_left_active: bool,
+    pub hotkey_right_active: bool,
+}
@@ -0,0 +1,184 @@
+use serde::Serialize;
+use std::any::Any;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use ts_rs::TS;
+
+use crate::clicker_state::{ClickerState, StopReason};
+use crate::hotkey_capture::HotkeyCapture;
+use crate::logging::target;
+use crate::settings;
+
+const FIRST_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(30);
+/// A worker that ran this long before crashing restarts without backoff.
+const STABLE_RUN: Duration = Duration::from_secs(60);
+const JOIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Locking that keeps going after another thread panicked with the lock held.
+/// Nothing guarded in this app is left half-updated across a panic point, so
+/// the data behind a poisoned lock is still usable.
+pub trait MutexExt<T> {
+    fn lock_unpoisoned(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> MutexExt<T> for Mutex<T> {
+    fn lock_unpoisoned(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A long-running background thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum Worker {
+    Clicker,
+    Hotkeys,
+}
+
+impl Worker {
+    fn name(self) -> &'static str {
+        match self {
+            Worker::Clicker => "clicker",
+            Worker::Hotkeys => "hotkeys",
+        }
+    }
+}
+
+/// Payload of the `worker-crashed` event.
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerCrash {
+    pub worker: Worker,
+    /// The panic message.
+    pub message: String,
+    /// Restarts of this worker so far, including the one about to happen.
+    pub restarts: u32,
+}
+
+/// The supervisor threads, kept so shutdown can wait for them.
+#[derive(Default)]
+pub struct Supervisor {
+    threads: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Supervisor {
+    /// Waits up to `timeout` for every supervised thread to finish. Returns
+    /// whether they all did; threads still running are left to the process exit.
+    pub fn join(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut threads = self.threads.lock_unpoisoned();
+        while threads.iter().any(|thread| !thread.is_finished()) {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(JOIN_POLL_INTERVAL);
+        }
+        for thread in threads.drain(..) {
+            let _ = thread.join();
+        }
+        true
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Gets back to a safe state after a worker died: stops the clicker (which
+/// releases held buttons), reloads settings from the store and tells the UI.
+fn recover(app_handle: &tauri::AppHandle, crash: WorkerCrash) {
+    log::error!(
+        target: target::APP,
+        "The {} thread crashed, restarting it: {}",
+        crash.worker.name(),
+        crash.message
+    );
+    let state = app_handle.state::<ClickerState>();
+    if state.is_running() {
+        state.stop(StopReason::WorkerCrashed);
+    }
+    if crash.worker == Worker::Hotkeys {
+        // The restarted loop starts without the capture session.
+        app_handle.state::<HotkeyCapture>().cancel();
+    }
+    settings::reload(app_handle);
+    app_handle.emit("worker-crashed", crash).unwrap_or_else(
+        |e| log::warn!(target: target::APP, "Failed to emit worker-crashed: {}", e),
+    );
+}
+
+/// Runs `run` on its own thread and restarts it, with backoff, whenever it
+/// panics. A worker that returns normally, or crashes during shutdown, is not
+/// restarted.
+pub fn supervise(app_handle: tauri::AppHandle, worker: Worker, run: fn(&tauri::AppHandle)) {
+    let supervisor = app_handle.clone();
+    let spawned = thread::Builder::new()
+        .name(format!("{}-supervisor", worker.name()))
+        .spawn(move || {
+            let mut restarts = 0;
+            let mut delay = FIRST_RESTART_DELAY;
+            loop {
+                let started = Instant::now();
+                let handle = app_handle.clone();
+                let result = thread::Builder::new()
+                    .name(worker.name().to_string())
+                    .spawn(move || run(&handle))
+                    .map_err(|e| e.to_string())
+                    .and_then(|thread| thread.join().map_err(|e| panic_message(&*e)));
+                let state = app_handle.state::<ClickerState>();
+                let message = match result {
+                    Ok(()) if state.is_shutting_down() => return,
+                    Ok(()) => {
+                        log::warn!(target: target::APP, "The {} thread exited", worker.name());
+                        return;
+                    }
+                    Err(message) if state.is_shutting_down() => {
+                        log::error!(
+                            target: target::APP,
+                            "The {} thread crashed during shutdown: {}",
+                            worker.name(),
+                            message
+                        );
+                        return;
+                    }
+                    Err(message) => message,
+                };
+
+                if started.elapsed() >= STABLE_RUN {
+                    delay = FIRST_RESTART_DELAY;
+                }
+                restarts += 1;
+                recover(
+                    &app_handle,
+                    WorkerCrash {
+                        worker,
+                        message,
+                        restarts,
+                    },
+                );
+                if state.wait_for_shutdown(delay) {
+                    return;
+                }
+                delay = (delay * 2).min(MAX_RESTART_DELAY);
+            }
+        });
+    match spawned {
+        Ok(thread) => supervisor
+            .state::<Supervisor>()
+            .threads
+            .lock_unpoisoned()
+            .push(thread),
+        Err(e) => {
+            log::error!(target: target::APP, "Failed to start the {} thread: {}", worker.name(), e)
+        }
+    }
+}
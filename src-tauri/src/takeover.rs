@@ -0,0 +1,37 @@
+use std::sync::Mutex;
+
+use crate::supervisor::MutexExt;
+
+/// Detects the user grabbing the mouse while a channel is clicking. The cursor is
+/// compared against an anchor moved to it on every click, so only movement since
+/// the last click counts, not drift over the whole session. Anything that moves
+/// the cursor on the clicker's behalf has to `reset` the guard afterwards, so the
+/// next poll re-anchors at the new position instead of treating it as the user.
+#[derive(Default)]
+pub struct CursorGuard {
+    anchor: Mutex<Option<(i32, i32)>>,
+}
+
+impl CursorGuard {
+    pub fn reset(&self) {
+        *self.anchor.lock_unpoisoned() = None;
+    }
+
+    /// Moves the anchor to where the cursor was when a click went out.
+    pub fn clicked(&self, position: (i32, i32)) {
+        *self.anchor.lock_unpoisoned() = Some(position);
+    }
+
+    /// Whether the cursor is more than `threshold` pixels from the anchor. The
+    /// first call after a reset anchors at `position`.
+    pub fn moved_beyond(&self, position: (i32, i32), threshold: u32) -> bool {
+        let mut anchor = self.anchor.lock_unpoisoned();
+        let Some((x, y)) = *anchor else {
+            *anchor = Some(position);
+            return false;
+        };
+        let dx = (position.0 - x) as f64;
+        let dy = (position.1 - y) as f64;
+        dx.hypot(dy) > threshold as f64
+    }
+}
@@ -0,0 +1,559 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+use tauri_store::StoreState;
+use ts_rs::TS;
+
+use crate::profiles::{self, Profile, ProfileError};
+use crate::settings::{self, AutoclickerSettings, SettingsError};
+
+/// Version of the portable file layout, independent of the store's schema version.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// A standalone settings file. A full export carries the global settings and every
+/// profile; a single-profile export carries only that profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFile {
+    pub format_version: u32,
+    pub app_version: String,
+    /// Raw settings so imports of an older schema go through the store migrations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<StoreState>,
+    #[serde(default, deserialize_with = "deserialize_profiles")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Profiles are read through the same upgrade the store migrations apply, so
+/// files exported by older versions keep their click speeds.
+fn deserialize_profiles<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Profile>, D::Error> {
+    let raw =
+        BTreeMap::<String, serde_json::Map<String, serde_json::Value>>::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|(name, mut values)| {
+            settings::split_click_speed(&mut values);
+            serde_json::from_value(values.into())
+                .map(|profile| (name, profile))
+                .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictStrategy {
+    #[default]
+    Rename,
+    Overwrite,
+    Skip,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rename" => Ok(ConflictStrategy::Rename),
+            "overwrite" => Ok(ConflictStrategy::Overwrite),
+            "skip" => Ok(ConflictStrategy::Skip),
+            _ => Err(format!(
+                "unknown conflict strategy \"{}\" (expected rename, overwrite or skip)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub added: Vec<String>,
+    pub overwritten: Vec<String>,
+    /// Imported name -> name it was stored under.
+    pub renamed: BTreeMap<String, String>,
+    pub skipped: Vec<String>,
+    pub settings_replaced: bool,
+}
+
+#[derive(Debug)]
+pub enum TransferError {
+    Io(std::io::Error),
+    Parse(String),
+    Serialize(String),
+    UnsupportedVersion(u32),
+    Invalid(Vec<SettingsError>),
+    Profile(ProfileError),
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::Io(e) => write!(f, "{}", e),
+            TransferError::Parse(e) => write!(f, "not a valid settings file: {}", e),
+            TransferError::Serialize(e) => write!(f, "failed to write settings: {}", e),
+            TransferError::UnsupportedVersion(version) => write!(
+                f,
+                "file format version {} is newer than the supported version {}",
+                version, EXPORT_FORMAT_VERSION
+            ),
+            TransferError::Invalid(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "invalid settings: {}", errors.join(", "))
+            }
+            TransferError::Profile(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for TransferError {
+    fn from(e: std::io::Error) -> Self {
+        TransferError::Io(e)
+    }
+}
+
+impl From<ProfileError> for TransferError {
+    fn from(e: ProfileError) -> Self {
+        TransferError::Profile(e)
+    }
+}
+
+impl From<tauri_store::Error> for TransferError {
+    fn from(e: tauri_store::Error) -> Self {
+        TransferError::Profile(ProfileError::Store(e))
+    }
+}
+
+impl Serialize for TransferError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+pub fn build_export(
+    app_handle: &tauri::AppHandle,
+    settings: &AutoclickerSettings,
+    profile: Option<&str>,
+) -> Result<ExportFile, TransferError> {
+    let mut settings = settings.clone();
+    settings.store_active_profile();
+
+    let (full, profiles) = match profile {
+        Some(name) => {
+            let profile = settings
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ProfileError::NotFound(name.to_string()))?;
+            (None, BTreeMap::from([(name.to_string(), profile)]))
+        }
+        None => {
+            let profiles = std::mem::take(&mut settings.profiles);
+            let mut state = settings.to_state();
+            state.remove(crate::store_keys::autoclicker_keys::PROFILES);
+            (Some(state), profiles)
+        }
+    };
+
+    Ok(ExportFile {
+        format_version: EXPORT_FORMAT_VERSION,
+        app_version: app_handle.package_info().version.to_string(),
+        settings: full,
+        profiles,
+    })
+}
+
+pub fn write_file(path: &Path, file: &ExportFile) -> Result<(), TransferError> {
+    let contents = if is_toml(path) {
+        toml::to_string_pretty(file).map_err(|e| TransferError::Serialize(e.to_string()))?
+    } else {
+        serde_json::to_string_pretty(file).map_err(|e| TransferError::Serialize(e.to_string()))?
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+pub fn read_file(path: &Path) -> Result<ExportFile, TransferError> {
+    let contents = fs::read_to_string(path)?;
+    let file: ExportFile = if is_toml(path) {
+        toml::from_str(&contents).map_err(|e| TransferError::Parse(e.to_string()))?
+    } else {
+        serde_json::from_str(&contents).map_err(|e| TransferError::Parse(e.to_string()))?
+    };
+    if file.format_version > EXPORT_FORMAT_VERSION {
+        return Err(TransferError::UnsupportedVersion(file.format_version));
+    }
+    Ok(file)
+}
+
+fn validate_profile(name: &str, profile: &Profile) -> Result<(), TransferError> {
+    profiles::validate_name(name)?;
+    AutoclickerSettings {
+        left_channel: profile.left_channel,
+        right_channel: profile.right_channel,
+        ..Default::default()
+    }
+    .validate()
+    .map_err(TransferError::Invalid)
+}
+
+fn free_name(settings: &AutoclickerSettings, name: &str) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !settings.profiles.contains_key(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Merges an import into `settings`. The merge is checked as a whole before it
+/// replaces them, so a bad file leaves the settings untouched. Hotkey errors the
+/// settings already had don't block it.
+pub fn apply_import(
+    settings: &mut AutoclickerSettings,
+    file: ExportFile,
+    on_conflict: ConflictStrategy,
+) -> Result<ImportSummary, TransferError> {
+    let mut merged = settings.clone();
+    let summary = merge_import(&mut merged, file, on_conflict)?;
+
+    let existing = profiles::hotkey_errors(settings, None);
+    let errors: Vec<String> = profiles::hotkey_errors(&merged, None)
+        .into_iter()
+        .filter(|error| !existing.contains(error))
+        .collect();
+    if !errors.is_empty() {
+        return Err(ProfileError::InvalidHotkey(errors).into());
+    }
+
+    *settings = merged;
+    Ok(summary)
+}
+
+fn merge_import(
+    settings: &mut AutoclickerSettings,
+    file: ExportFile,
+    on_conflict: ConflictStrategy,
+) -> Result<ImportSummary, TransferError> {
+    let (imported_settings, imported_profiles) = match file.settings {
+        Some(mut state) => {
+            // Exports from before profiles existed get theirs from the migration.
+            settings::migrate(&mut state);
+            let mut imported = AutoclickerSettings::from_state(&state);
+            if !file.profiles.is_empty() {
+                imported.profiles = file.profiles;
+            }
+            imported.validate().map_err(TransferError::Invalid)?;
+            let profiles = std::mem::take(&mut imported.profiles);
+            (Some(imported), profiles)
+        }
+        None => (None, file.profiles),
+    };
+    for (name, profile) in &imported_profiles {
+        validate_profile(name, profile)?;
+    }
+
+    let mut summary = ImportSummary::default();
+    let mut stored_as: BTreeMap<String, String> = BTreeMap::new();
+    settings.store_active_profile();
+
+    for (name, profile) in imported_profiles {
+        let target = if !settings.profiles.contains_key(&name) {
+            summary.added.push(name.clone());
+            name.clone()
+        } else {
+            match on_conflict {
+                ConflictStrategy::Skip => {
+                    summary.skipped.push(name);
+                    continue;
+                }
+                ConflictStrategy::Overwrite => {
+                    summary.overwritten.push(name.clone());
+                    name.clone()
+                }
+                ConflictStrategy::Rename => {
+                    let renamed = free_name(settings, &name);
+                    summary.renamed.insert(name.clone(), renamed.clone());
+                    renamed
+                }
+            }
+        };
+        settings.profiles.insert(target.clone(), profile);
+        stored_as.insert(name, target);
+    }
+
+    if let Some(imported) = imported_settings {
+        let active = stored_as
+            .get(&imported.active_profile)
+            .cloned()
+            .unwrap_or_else(|| settings.active_profile.clone());
+        *settings = AutoclickerSettings {
+            profiles: std::mem::take(&mut settings.profiles),
+            active_profile: std::mem::take(&mut settings.active_profile),
+            ..imported
+        };
+        // The live keys came with the file; make them match the active profile.
+        settings.activate_profile(&active)?;
+        summary.settings_replaced = true;
+    } else if summary.overwritten.contains(&settings.active_profile) {
+        // The active profile was replaced; load its new values into the live keys.
+        settings.activate_profile(&settings.active_profile.clone())?;
+    }
+
+    Ok(summary)
+}
+
+pub fn export_to(
+    app_handle: &tauri::AppHandle,
+    path: &Path,
+    profile: Option<&str>,
+) -> Result<(), TransferError> {
+    let settings = app_handle.state::<settings::SharedSettings>().snapshot();
+    let file = build_export(app_handle, &settings, profile)?;
+    write_file(path, &file)
+}
+
+pub fn import_from(
+    app_handle: &tauri::AppHandle,
+    path: &Path,
+    on_conflict: ConflictStrategy,
+) -> Result<ImportSummary, TransferError> {
+    let file = read_file(path)?;
+    settings::update(app_handle, |settings| {
+        apply_import(settings, file, on_conflict)
+    })
+}
+
+#[tauri::command]
+pub fn export_settings(
+    app_handle: tauri::AppHandle,
+    path: String,
+    profile: Option<String>,
+) -> Result<(), TransferError> {
+    export_to(&app_handle, Path::new(&path), profile.as_deref())
+}
+
+#[tauri::command]
+pub fn import_settings(
+    app_handle: tauri::AppHandle,
+    path: String,
+    on_conflict: Option<ConflictStrategy>,
+) -> Result<ImportSummary, TransferError> {
+    import_from(
+        &app_handle,
+        Path::new(&path),
+        on_conflict.unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::DEFAULT_PROFILE;
+    use crate::store_keys::autoclicker_keys;
+
+    fn profile(click_speed: f64, switch_hotkey: &str) -> Profile {
+        let timing = settings::ChannelTiming {
+            click_speed,
+            ..Default::default()
+        };
+        Profile {
+            left_channel: timing,
+            right_channel: timing,
+            switch_hotkey: switch_hotkey.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn profiles_file(profiles: &[(&str, Profile)]) -> ExportFile {
+        ExportFile {
+            format_version: EXPORT_FORMAT_VERSION,
+            app_version: "0.0.0".to_string(),
+            settings: None,
+            profiles: profiles
+                .iter()
+                .map(|(name, profile)| (name.to_string(), profile.clone()))
+                .collect(),
+        }
+    }
+
+    fn file_with_default_and_fast() -> ExportFile {
+        profiles_file(&[
+            (DEFAULT_PROFILE, profile(50.0, "")),
+            ("Fast", profile(10.0, "F1")),
+        ])
+    }
+
+    #[test]
+    fn renames_conflicting_profiles() {
+        let mut settings = AutoclickerSettings::default();
+        let summary = apply_import(
+            &mut settings,
+            file_with_default_and_fast(),
+            ConflictStrategy::Rename,
+        )
+        .unwrap();
+
+        assert_eq!(summary.added, ["Fast"]);
+        assert_eq!(
+            summary.renamed,
+            BTreeMap::from([(DEFAULT_PROFILE.to_string(), "Default (2)".to_string())])
+        );
+        assert_eq!(
+            settings.profiles["Default (2)"].left_channel.click_speed,
+            50.0
+        );
+        assert_eq!(
+            settings.profiles[DEFAULT_PROFILE].left_channel.click_speed,
+            100.0
+        );
+        assert_eq!(settings.left_channel.click_speed, 100.0);
+    }
+
+    #[test]
+    fn overwrites_conflicting_profiles() {
+        let mut settings = AutoclickerSettings::default();
+        let summary = apply_import(
+            &mut settings,
+            file_with_default_and_fast(),
+            ConflictStrategy::Overwrite,
+        )
+        .unwrap();
+
+        assert_eq!(summary.added, ["Fast"]);
+        assert_eq!(summary.overwritten, [DEFAULT_PROFILE]);
+        assert_eq!(
+            settings.profiles[DEFAULT_PROFILE].left_channel.click_speed,
+            50.0
+        );
+        // The active profile was overwritten, so its new values are live.
+        assert_eq!(settings.left_channel.click_speed, 50.0);
+    }
+
+    #[test]
+    fn skips_conflicting_profiles() {
+        let mut settings = AutoclickerSettings::default();
+        let summary = apply_import(
+            &mut settings,
+            file_with_default_and_fast(),
+            ConflictStrategy::Skip,
+        )
+        .unwrap();
+
+        assert_eq!(summary.added, ["Fast"]);
+        assert_eq!(summary.skipped, [DEFAULT_PROFILE]);
+        assert_eq!(settings.profiles.len(), 2);
+        assert_eq!(
+            settings.profiles[DEFAULT_PROFILE].left_channel.click_speed,
+            100.0
+        );
+    }
+
+    #[test]
+    fn imports_the_global_settings_of_a_full_export() {
+        let mut exported = AutoclickerSettings {
+            mouse_move_threshold: 5,
+            ..Default::default()
+        };
+        exported.left_channel.click_speed = 40.0;
+        exported.store_active_profile();
+        let profiles = std::mem::take(&mut exported.profiles);
+        let mut state = exported.to_state();
+        state.remove(autoclicker_keys::PROFILES);
+        let file = ExportFile {
+            settings: Some(state),
+            profiles,
+            ..profiles_file(&[])
+        };
+
+        let mut settings = AutoclickerSettings::default();
+        let summary = apply_import(&mut settings, file, ConflictStrategy::Rename).unwrap();
+
+        assert!(summary.settings_replaced);
+        assert_eq!(settings.mouse_move_threshold, 5);
+        // The imported active profile was renamed and stays active under its new name.
+        assert_eq!(settings.active_profile, "Default (2)");
+        assert_eq!(settings.left_channel.click_speed, 40.0);
+        assert_eq!(
+            settings.profiles[DEFAULT_PROFILE].left_channel.click_speed,
+            100.0
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_click_speeds() {
+        let mut settings = AutoclickerSettings::default();
+        let before = settings.clone();
+        let file = profiles_file(&[("Broken", profile(0.0, ""))]);
+
+        let result = apply_import(&mut settings, file, ConflictStrategy::Rename);
+        assert!(matches!(result, Err(TransferError::Invalid(_))));
+        assert_eq!(settings, before);
+    }
+
+    #[test]
+    fn rejects_unknown_hotkeys() {
+        let mut settings = AutoclickerSettings::default();
+        let before = settings.clone();
+        let broken = Profile {
+            hotkey_left: "Hyper".to_string(),
+            ..profile(100.0, "")
+        };
+        let file = profiles_file(&[("Broken", broken)]);
+
+        let result = apply_import(&mut settings, file, ConflictStrategy::Rename);
+        assert!(matches!(
+            result,
+            Err(TransferError::Profile(ProfileError::InvalidHotkey(_)))
+        ));
+        assert_eq!(settings, before);
+    }
+
+    #[test]
+    fn rejects_switch_hotkeys_shared_after_the_merge() {
+        let mut settings = AutoclickerSettings::default();
+        settings
+            .profiles
+            .insert("Slow".to_string(), profile(500.0, "F1"));
+        let before = settings.clone();
+
+        // "F1" is free in the file, but "Slow" already switches with it.
+        let result = apply_import(
+            &mut settings,
+            file_with_default_and_fast(),
+            ConflictStrategy::Rename,
+        );
+        assert!(matches!(
+            result,
+            Err(TransferError::Profile(ProfileError::InvalidHotkey(_)))
+        ));
+        assert_eq!(settings, before);
+    }
+
+    #[test]
+    fn rejects_files_from_a_newer_version() {
+        let path = std::env::temp_dir().join(format!(
+            "autoclicker-transfer-test-{}.json",
+            std::process::id()
+        ));
+        let file = ExportFile {
+            format_version: EXPORT_FORMAT_VERSION + 1,
+            ..profiles_file(&[])
+        };
+        write_file(&path, &file).unwrap();
+
+        let result = read_file(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(TransferError::UnsupportedVersion(version)) if version == EXPORT_FORMAT_VERSION + 1
+        ));
+    }
+}
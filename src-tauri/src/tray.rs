@@ -0,0 +1,286 @@
+use tauri::image::Image;
+use tauri::menu::{
+    CheckMenuItemBuilder, Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder,
+};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{Manager, Window, WindowEvent};
+
+use crate::clicker::{self, Channel};
+use crate::clicker_state::{ClickerPhase, ClickerState, StopReason};
+use crate::logging::target;
+use crate::profiles;
+use crate::settings::{AutoclickerSettings, SharedSettings};
+
+const TRAY_ID: &str = "main";
+const MAIN_WINDOW: &str = "main";
+/// Click intervals offered in the tray menu, in milliseconds.
+const PRESET_SPEEDS: [f64; 6] = [10.0, 20.0, 50.0, 100.0, 250.0, 1000.0];
+const ICON_SIZE: u32 = 32;
+
+mod menu_ids {
+    pub const STATUS: &str = "status";
+    pub const TOGGLE_RUNNING: &str = "toggle-running";
+    pub const TOGGLE_WINDOW: &str = "toggle-window";
+    pub const QUIT: &str = "quit";
+    pub const PROFILE_PREFIX: &str = "profile:";
+    pub const SPEED_PREFIX: &str = "speed:";
+}
+
+fn status_text(phase: ClickerPhase) -> String {
+    match phase {
+        ClickerPhase::Idle => "Idle".to_string(),
+        ClickerPhase::Armed => "Listening for hotkeys".to_string(),
+        ClickerPhase::Clicking(channels) => {
+            let active: Vec<String> = channels.active().map(|c| format!("{:?}", c)).collect();
+            format!("Clicking: {}", active.join(" + "))
+        }
+        ClickerPhase::Stopping => "Stopping".to_string(),
+    }
+}
+
+/// A dot whose left and right halves show the two channels: grey when idle,
+/// cyan while listening and green for a channel that is clicking.
+fn status_icon(phase: ClickerPhase) -> Image<'static> {
+    const IDLE: [u8; 3] = [148, 163, 184];
+    const ARMED: [u8; 3] = [34, 211, 238];
+    const CLICKING: [u8; 3] = [74, 222, 128];
+
+    let color = |channel: Channel| match phase {
+        _ if phase.channels().get(channel) => CLICKING,
+        ClickerPhase::Armed | ClickerPhase::Clicking(_) => ARMED,
+        _ => IDLE,
+    };
+    let center = (ICON_SIZE as f32 - 1.0) / 2.0;
+    let radius = ICON_SIZE as f32 / 2.0 - 2.0;
+    let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            // One pixel of anti-aliasing at the edge.
+            let alpha = (radius + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            let channel = if dx < 0.0 {
+                Channel::Left
+            } else {
+                Channel::Right
+            };
+            rgba.extend(color(channel));
+            rgba.push((alpha * 255.0) as u8);
+        }
+    }
+    Image::new_owned(rgba, ICON_SIZE, ICON_SIZE)
+}
+
+/// Menu labels treat `&` as a mnemonic marker.
+fn escape(text: &str) -> String {
+    text.replace('&', "&&")
+}
+
+fn window_visible(app_handle: &tauri::AppHandle) -> bool {
+    app_handle
+        .get_webview_window(MAIN_WINDOW)
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false)
+}
+
+fn build_menu(
+    app_handle: &tauri::AppHandle,
+    phase: ClickerPhase,
+    settings: &AutoclickerSettings,
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let status = MenuItemBuilder::with_id(menu_ids::STATUS, status_text(phase))
+        .enabled(false)
+        .build(app_handle)?;
+    let toggle_running = MenuItemBuilder::with_id(
+        menu_ids::TOGGLE_RUNNING,
+        if phase.is_running() { "Stop" } else { "Start" },
+    )
+    .build(app_handle)?;
+
+    let mut profile_menu = SubmenuBuilder::new(app_handle, "Profile");
+    for name in settings.profiles.keys() {
+        let item = CheckMenuItemBuilder::with_id(
+            format!("{}{}", menu_ids::PROFILE_PREFIX, name),
+            escape(name),
+        )
+        .checked(*name == settings.active_profile)
+        .build(app_handle)?;
+        profile_menu = profile_menu.item(&item);
+    }
+
+    let mut speed_menu = SubmenuBuilder::new(app_handle, "Speed");
+    for speed in PRESET_SPEEDS {
+        let item = CheckMenuItemBuilder::with_id(
+            format!("{}{}", menu_ids::SPEED_PREFIX, speed),
+            format!("{} ms ({} CPS)", speed, 1000.0 / speed),
+        )
+        .checked(
+            Channel::ALL
+                .iter()
+                .all(|c| settings.channel(*c).click_speed == speed),
+        )
+        .build(app_handle)?;
+        speed_menu = speed_menu.item(&item);
+    }
+
+    let toggle_window = MenuItemBuilder::with_id(
+        menu_ids::TOGGLE_WINDOW,
+        if window_visible(app_handle) {
+            "Hide window"
+        } else {
+            "Show window"
+        },
+    )
+    .build(app_handle)?;
+    let quit = MenuItemBuilder::with_id(menu_ids::QUIT, "Quit").build(app_handle)?;
+
+    MenuBuilder::new(app_handle)
+        .item(&status)
+        .separator()
+        .item(&toggle_running)
+        .item(&profile_menu.build()?)
+        .item(&speed_menu.build()?)
+        .separator()
+        .item(&toggle_window)
+        .item(&quit)
+        .build()
+}
+
+/// Redraws the tray from the current clicker state and settings. Must run on the
+/// main thread; use `schedule_refresh` from anywhere else.
+pub fn refresh(app_handle: &tauri::AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let phase = app_handle
+        .try_state::<ClickerState>()
+        .map_or(ClickerPhase::Idle, |state| state.phase());
+    let settings = app_handle.state::<SharedSettings>().snapshot();
+    let result = build_menu(app_handle, phase, &settings)
+        .and_then(|menu| tray.set_menu(Some(menu)))
+        .and_then(|_| tray.set_icon(Some(status_icon(phase))))
+        .and_then(|_| tray.set_tooltip(Some(format!("Autoclicker: {}", status_text(phase)))));
+    if let Err(e) = result {
+        log::warn!(target: target::APP, "Failed to update the tray: {}", e);
+    }
+}
+
+/// Runs `refresh` on the main thread: queued from other threads, right away on
+/// the main thread itself. `refresh` reads the clicker state, so never call this
+/// with it locked.
+pub fn schedule_refresh(app_handle: &tauri::AppHandle) {
+    let handle = app_handle.clone();
+    if let Err(e) = app_handle.run_on_main_thread(move || refresh(&handle)) {
+        log::warn!(target: target::APP, "Failed to schedule a tray update: {}", e);
+    }
+}
+
+pub fn show_window(app_handle: &tauri::AppHandle) {
+    if let Some(window) = app_handle.get_webview_window(MAIN_WINDOW) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    schedule_refresh(app_handle);
+}
+
+fn toggle_window(app_handle: &tauri::AppHandle) {
+    match app_handle.get_webview_window(MAIN_WINDOW) {
+        Some(window) if window_visible(app_handle) => {
+            let _ = window.hide();
+            schedule_refresh(app_handle);
+        }
+        _ => show_window(app_handle),
+    }
+}
+
+fn on_menu_event(app_handle: &tauri::AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    let result = match id {
+        menu_ids::TOGGLE_RUNNING => {
+            let state = app_handle.state::<ClickerState>();
+            if state.is_running() {
+                state.stop(StopReason::User);
+                Ok(())
+            } else {
+                state.arm().map(|_| ()).map_err(|e| e.to_string())
+            }
+        }
+        menu_ids::TOGGLE_WINDOW => {
+            toggle_window(app_handle);
+            Ok(())
+        }
+        menu_ids::QUIT => {
+            app_handle.exit(0);
+            Ok(())
+        }
+        _ => {
+            if let Some(name) = id.strip_prefix(menu_ids::PROFILE_PREFIX) {
+                profiles::switch_profile(app_handle.clone(), name.to_string())
+                    .map_err(|e| e.to_string())
+            } else if let Some(speed) = id.strip_prefix(menu_ids::SPEED_PREFIX) {
+                let speed = speed.parse().unwrap_or_default();
+                clicker::set_speed(app_handle.clone(), None, speed, None)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            } else {
+                Ok(())
+            }
+        }
+    };
+    if let Err(e) = result {
+        log::error!(target: target::APP, "Tray action {} failed: {}", id, e);
+    }
+    // Check items toggle themselves when clicked; put them back in line with
+    // what actually happened.
+    refresh(app_handle);
+}
+
+fn on_tray_icon_event(tray: &TrayIcon, event: TrayIconEvent) {
+    if let TrayIconEvent::Click {
+        button: MouseButton::Left,
+        button_state: MouseButtonState::Up,
+        ..
+    } = event
+    {
+        show_window(tray.app_handle());
+    }
+}
+
+/// Creates the tray icon. Fails on desktops without a system tray.
+pub fn init(app_handle: &tauri::AppHandle) -> tauri::Result<()> {
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(status_icon(ClickerPhase::Idle))
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(on_tray_icon_event)
+        .build(app_handle)?;
+    refresh(app_handle);
+    Ok(())
+}
+
+/// Hides the main window instead of closing or minimizing it when
+/// `minimize_to_tray` is on.
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    let app_handle = window.app_handle();
+    if window.label() != MAIN_WINDOW || app_handle.tray_by_id(TRAY_ID).is_none() {
+        return;
+    }
+    if !app_handle
+        .state::<SharedSettings>()
+        .snapshot()
+        .minimize_to_tray
+    {
+        return;
+    }
+    match event {
+        WindowEvent::CloseRequested { api, .. } => {
+            api.prevent_close();
+            let _ = window.hide();
+        }
+        WindowEvent::Resized(_) if window.is_minimized().unwrap_or(false) => {
+            let _ = window.hide();
+        }
+        _ => return,
+    }
+    schedule_refresh(app_handle);
+}
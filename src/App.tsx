@@ -1,14 +1,55 @@
 import "./globals.css"
+import { listen } from "@tauri-apps/api/event"
 import { MousePointerClick, Play, Square } from "lucide-solid"
+import { createSignal, onCleanup, onMount, Show } from "solid-js"
 import { ThemeToggle } from "@/components/theme-toggle"
+import type { BackendStatus, WorkerCrash } from "@/lib/bindings"
+import { errorMessage, getBackendStatus } from "@/lib/commands"
 import { useTempStore } from "@/lib/temp-store"
 import { ThemeProvider } from "@/lib/theme-provider"
+import { DiagnosticsControl } from "./components/diagnostics-control"
+import { HistoryPanel } from "./components/history-panel"
 import { HotkeyControl } from "./components/hotkey-control"
+import { NotificationControl } from "./components/notification-control"
+import { OverlayControl } from "./components/overlay-control"
+import { ProfileControl } from "./components/profile-control"
+import { SafetyControl } from "./components/safety-control"
+import { SoundControl } from "./components/sound-control"
 import { SpeedControl } from "./components/speed-control"
+import { StatsPanel } from "./components/stats-panel"
+import { TransferControl } from "./components/transfer-control"
+import { WindowControl } from "./components/window-control"
 import { Button } from "./components/ui/button"
 
 function App() {
   const tempStore = useTempStore()
+  const [error, setError] = createSignal<string | null>(null)
+  const [backend, setBackend] = createSignal<BackendStatus>({ state: "connecting" })
+
+  onMount(() => {
+    const unlisten = [
+      listen<BackendStatus>("backend-status", (event) => setBackend(event.payload)),
+      listen<WorkerCrash>("worker-crashed", (event) =>
+        setError(`The ${event.payload.worker} thread crashed and was restarted: ${event.payload.message}`),
+      ),
+    ]
+    onCleanup(() => unlisten.forEach((pending) => pending.then((stop) => stop())))
+    getBackendStatus().then(setBackend)
+  })
+
+  const backendError = () => {
+    const status = backend()
+    return status.state === "failed" ? status.error : null
+  }
+
+  const toggleRunning = async () => {
+    try {
+      await tempStore.toggleIsRunning()
+      setError(null)
+    } catch (e) {
+      setError(errorMessage(e))
+    }
+  }
 
   return (
     <ThemeProvider>
@@ -35,15 +76,33 @@ function App() {
           </div>
         </header>
 
-        <main class="flex p-4 flex-col gap-4">
+        <main class="flex p-4 flex-col gap-4 overflow-y-auto">
+          <ProfileControl />
           <SpeedControl />
           <HotkeyControl isListening={tempStore.isRunning} />
+          <SafetyControl />
+          <WindowControl />
+          <OverlayControl />
+          <SoundControl />
+          <NotificationControl />
+          <TransferControl />
+          <StatsPanel />
+          <HistoryPanel />
+          <DiagnosticsControl />
         </main>
 
-        <div class="p-4 flex flex-1 items-end">
+        <div class="p-4 flex flex-1 flex-col justify-end gap-2">
+          <Show when={backendError()}>
+            <p class="text-xs text-red-500">
+              Can't send clicks: {backendError()}. Retrying in the background.
+            </p>
+          </Show>
+          <Show when={error()}>
+            <p class="text-xs text-red-500">{error()}</p>
+          </Show>
           <Button
             class={`w-full ${tempStore.isRunning ? "bg-red-600 hover:bg-red-700" : ""}`}
-            onClick={tempStore.toggleIsRunning}
+            onClick={toggleRunning}
           >
             {tempStore.isRunning ? (
               <>
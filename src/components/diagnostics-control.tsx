@@ -0,0 +1,76 @@
+import { save } from "@tauri-apps/plugin-dialog"
+import { Bug, ScrollText } from "lucide-solid"
+import { createSignal, For, Show } from "solid-js"
+import { Button } from "@/components/ui/button"
+import { Label } from "@/components/ui/label"
+import { collectDiagnostics, errorMessage, getRecentLogs } from "@/lib/commands"
+
+const SHOWN_LOG_LINES = 100
+
+export function DiagnosticsControl() {
+  const [logs, setLogs] = createSignal<string[] | null>(null)
+  const [message, setMessage] = createSignal<string | null>(null)
+  const [error, setError] = createSignal<string | null>(null)
+
+  const toggleLogs = async () => {
+    if (logs()) {
+      setLogs(null)
+      return
+    }
+    try {
+      setLogs(await getRecentLogs(SHOWN_LOG_LINES))
+      setError(null)
+    } catch (e) {
+      setError(errorMessage(e))
+    }
+  }
+
+  const saveDiagnostics = async () => {
+    const path = await save({
+      defaultPath: "autoclicker-diagnostics.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    })
+    if (!path) return
+    try {
+      await collectDiagnostics(path)
+      setError(null)
+      setMessage(`Saved to ${path}`)
+    } catch (e) {
+      setError(errorMessage(e))
+    }
+  }
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <Label class="text-sm font-medium">Diagnostics</Label>
+        <div class="flex items-center gap-2">
+          <Button variant="outline" size="sm" class="h-7 text-xs" onClick={toggleLogs}>
+            <ScrollText class="h-3 w-3 mr-1" />
+            {logs() ? "Hide logs" : "Show logs"}
+          </Button>
+          <Button variant="outline" size="sm" class="h-7 text-xs" onClick={saveDiagnostics}>
+            <Bug class="h-3 w-3 mr-1" />
+            Save report
+          </Button>
+        </div>
+      </div>
+
+      <Show when={error()}>
+        <p class="text-xs text-red-500">{error()}</p>
+      </Show>
+      <Show when={!error() && message()}>
+        <p class="text-xs text-muted-foreground">{message()}</p>
+      </Show>
+      <Show when={logs()}>
+        {(lines) => (
+          <pre class="max-h-48 overflow-auto rounded bg-muted/30 p-2 text-[10px] leading-tight">
+            <Show when={lines().length > 0} fallback="No log entries yet">
+              <For each={lines()}>{(line) => <div>{line}</div>}</For>
+            </Show>
+          </pre>
+        )}
+      </Show>
+    </div>
+  )
+}
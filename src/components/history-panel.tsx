@@ -0,0 +1,95 @@
+import { listen } from "@tauri-apps/api/event"
+import { History, Trash2 } from "lucide-solid"
+import { createSignal, For, onCleanup, onMount, Show } from "solid-js"
+import { Button } from "@/components/ui/button"
+import { Label } from "@/components/ui/label"
+import type { ClickerPhase, SessionRecord, StopReason } from "@/lib/bindings"
+import { clearHistory, errorMessage, getHistory } from "@/lib/commands"
+
+const SHOWN_SESSIONS = 10
+
+const reasons: Record<StopReason, string> = {
+  user: "Stopped",
+  emergencyStop: "Emergency stop",
+  failsafe: "Fail-safe",
+  userTookOver: "Took over",
+  backendError: "Input error",
+  workerCrashed: "Crashed",
+  appExit: "App closed",
+}
+
+const duration = (ms: number) => {
+  const seconds = Math.round(ms / 1000)
+  return seconds < 60 ? `${seconds}s` : `${Math.floor(seconds / 60)}m ${seconds % 60}s`
+}
+
+export function HistoryPanel() {
+  const [sessions, setSessions] = createSignal<SessionRecord[]>([])
+  const [error, setError] = createSignal<string | null>(null)
+
+  const refresh = async () => {
+    try {
+      setSessions(await getHistory(SHOWN_SESSIONS))
+      setError(null)
+    } catch (e) {
+      setError(errorMessage(e))
+    }
+  }
+
+  const clear = async () => {
+    try {
+      await clearHistory()
+      await refresh()
+    } catch (e) {
+      setError(errorMessage(e))
+    }
+  }
+
+  onMount(() => {
+    // A session is written when the clicker goes back to idle.
+    const unlisten = listen<ClickerPhase>("clicker-state", (event) => {
+      if (event.payload === "idle") refresh()
+    })
+    onCleanup(() => unlisten.then((stop) => stop()))
+    refresh()
+  })
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <History class="h-4 w-4 text-muted-foreground" />
+          <Label class="text-sm font-medium">Recent sessions</Label>
+        </div>
+        <Button variant="outline" size="sm" class="h-7 text-xs" onClick={clear}>
+          <Trash2 class="h-3 w-3 mr-1" />
+          Clear
+        </Button>
+      </div>
+
+      <Show when={error()}>
+        <p class="text-xs text-red-500">{error()}</p>
+      </Show>
+      <Show
+        when={sessions().length > 0}
+        fallback={<p class="text-xs text-muted-foreground">No sessions yet</p>}
+      >
+        <ul class="text-xs text-muted-foreground space-y-1">
+          <For each={sessions()}>
+            {(session) => (
+              <li class="flex justify-between gap-2">
+                <span>
+                  {new Date(session.startedAt).toLocaleString()} · {session.profile}
+                </span>
+                <span>
+                  {session.clicks} clicks in {duration(session.endedAt - session.startedAt)} ·{" "}
+                  {reasons[session.stopReason]}
+                </span>
+              </li>
+            )}
+          </For>
+        </ul>
+      </Show>
+    </div>
+  )
+}
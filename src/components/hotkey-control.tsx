@@ -1,11 +1,13 @@
+import { invoke } from "@tauri-apps/api/core"
 import { MousePointer } from "lucide-solid"
-import { createEffect, createSignal, onCleanup } from "solid-js"
+import { createEffect, createSignal, For, onCleanup, Show } from "solid-js"
 import { useAutoclickerStore } from "@/lib/autoclicker-store"
+import type { HotkeyIssue, HotkeyValidation } from "@/lib/bindings"
 import { useTempStore } from "@/lib/temp-store"
 import { Button } from "./ui/button"
 import { Label } from "./ui/label"
 
-type HotkeyType = "left" | "right"
+type HotkeyType = "left" | "right" | "panic"
 
 interface HotkeyControlProps {
   class?: string
@@ -55,125 +57,62 @@ export function HotkeyControl(props: HotkeyControlProps) {
   const tempStore = useTempStore()
 
   const [recording, setRecording] = createSignal<HotkeyType | null>(null)
-  const [currentModifiers, setCurrentModifiers] = createSignal<string[]>([])
-  const [currentKey, setCurrentKey] = createSignal<string>("")
-
-  const handleStartRecording = (type: HotkeyType): void => {
-    setRecording(type)
-    setCurrentModifiers([])
-    setCurrentKey("")
-  }
-
-  const handleInputDown = (e: KeyboardEvent | MouseEvent): void => {
-    if (!recording()) return
-    e.preventDefault()
-
-    if (e instanceof KeyboardEvent) {
-      if (e.key === "Escape") {
-        setRecording(null)
-        setCurrentModifiers([])
-        setCurrentKey("")
-        return
-      }
-
-      if (["Control", "Alt", "Shift", "Meta"].includes(e.key)) {
-        setCurrentModifiers((prev) => {
-          const modifier = e.key === "Control" ? "Ctrl" : e.key
-          return prev.includes(modifier) ? prev : [...prev, modifier]
-        })
-        return
-      }
-
-      setCurrentKey(e.key === " " ? "Space" : e.key)
-    } else if (e instanceof MouseEvent) {
-      if (e.button > 2) {
-        setCurrentKey(`MouseButton${e.button + 1}`)
-      }
+  const [issues, setIssues] = createSignal<HotkeyIssue[]>([])
+
+  const commitHotkey = async (type: HotkeyType, fullKey: string): Promise<void> => {
+    const bindings = {
+      hotkeyLeft: type === "left" ? fullKey : autoclickerStore.hotkeyLeft,
+      hotkeyRight: type === "right" ? fullKey : autoclickerStore.hotkeyRight,
+      panicHotkey: type === "panic" ? fullKey : autoclickerStore.panicHotkey,
+      ...Object.fromEntries(
+        Object.entries(autoclickerStore.profiles).map(([name, profile]) => [
+          `profile:${name}`,
+          profile.switchHotkey,
+        ]),
+      ),
+    }
+    const validation = await invoke<HotkeyValidation>("validate_hotkeys", { bindings })
+    setIssues(validation.issues)
+    if (!validation.valid) return
+
+    if (type === "left") {
+      autoclickerStore.setHotkeyLeft(fullKey)
+    } else if (type === "right") {
+      autoclickerStore.setHotkeyRight(fullKey)
+    } else {
+      autoclickerStore.setPanicHotkey(fullKey)
     }
   }
 
-  const handleInputUp = (e: KeyboardEvent | MouseEvent): void => {
-    if (!recording()) return
-
-    if (e instanceof KeyboardEvent) {
-      if (!["Control", "Alt", "Shift", "Meta", "Escape", " "].includes(e.key) && currentKey()) {
-        const fullKey = [...currentModifiers(), currentKey()].join("+")
-
-        if (recording() === "left") {
-          autoclickerStore.setHotkeyLeft(fullKey)
-        } else if (recording() === "right") {
-          autoclickerStore.setHotkeyRight(fullKey)
-        }
-
-        setRecording(null)
-        setCurrentModifiers([])
-        setCurrentKey("")
-      }
-
-      if (["Control", "Alt", "Shift", "Meta"].includes(e.key)) {
-        setCurrentModifiers((prev) =>
-          prev.filter((m) => m !== (e.key === "Control" ? "Ctrl" : e.key)),
-        )
-      }
-    } else if (e instanceof MouseEvent) {
-      e.preventDefault()
-      if (currentKey() && e.button > 2) {
-        const fullKey = currentKey()
-
-        if (recording() === "left") {
-          autoclickerStore.setHotkeyLeft(fullKey)
-        } else if (recording() === "right") {
-          autoclickerStore.setHotkeyRight(fullKey)
-        }
-
-        setRecording(null)
-        setCurrentModifiers([])
-        setCurrentKey("")
-      }
+  const handleStartRecording = async (type: HotkeyType): Promise<void> => {
+    if (recording()) return
+    setRecording(type)
+    try {
+      // The backend listener records the chord, so the result is always something it can match.
+      const hotkey = await invoke<string | null>("capture_hotkey")
+      if (hotkey) await commitHotkey(type, hotkey)
+    } finally {
+      setRecording(null)
     }
   }
 
   createEffect(() => {
-    const handleContextMenu = (e: Event) => e.preventDefault()
+    // Keep extra mouse buttons from navigating the webview while recording.
+    const preventDefault = (e: Event) => e.preventDefault()
 
     if (recording()) {
-      window.addEventListener("keydown", handleInputDown as EventListener)
-      window.addEventListener("keyup", handleInputUp as EventListener)
-      window.addEventListener("mousedown", handleInputDown as EventListener)
-      window.addEventListener("mouseup", handleInputUp as EventListener)
-      window.addEventListener("contextmenu", handleContextMenu)
+      window.addEventListener("keydown", preventDefault)
+      window.addEventListener("mouseup", preventDefault)
+      window.addEventListener("contextmenu", preventDefault)
 
       onCleanup(() => {
-        window.removeEventListener("keydown", handleInputDown as EventListener)
-        window.removeEventListener("keyup", handleInputUp as EventListener)
-        window.removeEventListener("mousedown", handleInputDown as EventListener)
-        window.removeEventListener("mouseup", handleInputUp as EventListener)
-        window.removeEventListener("contextmenu", handleContextMenu)
+        window.removeEventListener("keydown", preventDefault)
+        window.removeEventListener("mouseup", preventDefault)
+        window.removeEventListener("contextmenu", preventDefault)
       })
     }
   })
 
-  const getRecordingText = (): string => {
-    if (!recording()) return ""
-
-    const key = currentKey()
-    if (key.startsWith("MouseButton")) {
-      if (key === "MouseButton4") return "Browser Back"
-      if (key === "MouseButton5") return "Browser Forward"
-
-      const buttonNumber = key.replace("MouseButton", "")
-      return `Mouse Button ${buttonNumber}`
-    }
-
-    if (currentModifiers().length === 0 && !key) {
-      return "Press key..."
-    }
-
-    const parts = [...currentModifiers()]
-    if (key) parts.push(key)
-    return parts.join("+")
-  }
-
   return (
     <div
       class={`w-full max-w-md rounded-md border border-border/30 bg-transparent p-3 space-y-3 ${props.class || ""}`}
@@ -205,7 +144,7 @@ export function HotkeyControl(props: HotkeyControlProps) {
             isActive={tempStore.hotkeyLeftActive}
             isRegistered={!!autoclickerStore.hotkeyLeft}
             isListening={props.isListening ?? false}
-            recordingText={getRecordingText()}
+            recordingText="Press key..."
             onRecordClick={handleStartRecording}
           />
         </div>
@@ -227,12 +166,44 @@ export function HotkeyControl(props: HotkeyControlProps) {
             isActive={tempStore.hotkeyRightActive}
             isRegistered={!!autoclickerStore.hotkeyRight}
             isListening={props.isListening ?? false}
-            recordingText={getRecordingText()}
+            recordingText="Press key..."
+            onRecordClick={handleStartRecording}
+          />
+        </div>
+
+        <div class="flex items-center justify-between min-h-[28px]">
+          <div class="flex items-center gap-2">
+            <div class="w-2 h-2 rounded-full bg-red-400" />
+            <Label class="text-xs font-medium text-muted-foreground">Emergency stop</Label>
+          </div>
+
+          <HotkeyButton
+            type="panic"
+            hotkey={autoclickerStore.panicHotkey}
+            isRecording={recording() === "panic"}
+            isActive={false}
+            isRegistered={!!autoclickerStore.panicHotkey}
+            isListening={props.isListening ?? false}
+            recordingText="Press key..."
             onRecordClick={handleStartRecording}
           />
         </div>
       </div>
 
+      <Show when={issues().length > 0}>
+        <ul class="space-y-1">
+          <For each={issues()}>
+            {(issue) => (
+              <li
+                class={`text-xs ${issue.severity === "error" ? "text-red-500" : "text-yellow-600"}`}
+              >
+                {issue.message}
+              </li>
+            )}
+          </For>
+        </ul>
+      </Show>
+
       <p class="text-xs text-muted-foreground">
         Press a key. ESC to cancel. Hotkeys work globally.
       </p>
@@ -0,0 +1,56 @@
+import { Bell } from "lucide-solid"
+import { Show } from "solid-js"
+import { Label } from "@/components/ui/label"
+import { Switch } from "@/components/ui/switch"
+import { useAutoclickerStore } from "@/lib/autoclicker-store"
+
+export function NotificationControl() {
+  const store = useAutoclickerStore()
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <Bell class="h-4 w-4 text-muted-foreground" />
+          <Label for="notifications-switch" class="text-sm font-medium">
+            Desktop notifications
+          </Label>
+        </div>
+        <Switch
+          id="notifications-switch"
+          checked={store.notifications.enabled}
+          onCueChange={() => store.setNotifications({ enabled: !store.notifications.enabled })}
+        />
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Tells you why clicking stopped and how many clicks the session made, when it wasn't you
+        who stopped it
+      </p>
+
+      <Show when={store.notifications.enabled}>
+        <div class="flex items-center justify-between pt-2">
+          <Label for="notify-safety-switch" class="text-sm">
+            Fail-safe, panic hotkey and mouse takeover
+          </Label>
+          <Switch
+            id="notify-safety-switch"
+            checked={store.notifications.safetyStops}
+            onCueChange={() =>
+              store.setNotifications({ safetyStops: !store.notifications.safetyStops })
+            }
+          />
+        </div>
+        <div class="flex items-center justify-between">
+          <Label for="notify-errors-switch" class="text-sm">
+            Input errors and crashes
+          </Label>
+          <Switch
+            id="notify-errors-switch"
+            checked={store.notifications.errors}
+            onCueChange={() => store.setNotifications({ errors: !store.notifications.errors })}
+          />
+        </div>
+      </Show>
+    </div>
+  )
+}
@@ -0,0 +1,77 @@
+import { Layers } from "lucide-solid"
+import { For, Show } from "solid-js"
+import { Label } from "@/components/ui/label"
+import { Slider } from "@/components/ui/slider"
+import { Switch } from "@/components/ui/switch"
+import { type OverlayPosition, useAutoclickerStore } from "@/lib/autoclicker-store"
+
+const positions: { value: OverlayPosition; label: string }[] = [
+  { value: "topLeft", label: "Top left" },
+  { value: "topRight", label: "Top right" },
+  { value: "bottomLeft", label: "Bottom left" },
+  { value: "bottomRight", label: "Bottom right" },
+]
+
+export function OverlayControl() {
+  const store = useAutoclickerStore()
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <Layers class="h-4 w-4 text-muted-foreground" />
+          <Label for="overlay-switch" class="text-sm font-medium">
+            On-screen overlay
+          </Label>
+        </div>
+        <Switch
+          id="overlay-switch"
+          checked={store.overlay.enabled}
+          onCueChange={() => store.setOverlay({ enabled: !store.overlay.enabled })}
+        />
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Shows active channels, clicks per second and the session count on top of other windows
+        while the clicker runs
+      </p>
+
+      <Show when={store.overlay.enabled}>
+        <div class="flex items-center justify-between pt-2">
+          <Label for="overlay-position" class="text-sm">
+            Position
+          </Label>
+          <select
+            id="overlay-position"
+            class="h-7 rounded-md border border-input bg-background px-2 text-xs"
+            value={store.overlay.position}
+            onChange={(e) =>
+              store.setOverlay({ position: e.currentTarget.value as OverlayPosition })
+            }
+          >
+            <For each={positions}>
+              {(position) => <option value={position.value}>{position.label}</option>}
+            </For>
+          </select>
+        </div>
+
+        <div class="flex items-center justify-between pt-2">
+          <Label class="text-sm">Opacity</Label>
+          <span class="text-xs font-mono text-muted-foreground">
+            {Math.round(store.overlay.opacity * 100)}%
+          </span>
+        </div>
+        <Slider
+          value={[store.overlay.opacity * 100]}
+          minValue={20}
+          maxValue={100}
+          step={5}
+          onChange={(values) => {
+            const value = Array.isArray(values) ? values[0] : values
+            store.setOverlay({ opacity: value / 100 })
+          }}
+          class="my-4"
+        />
+      </Show>
+    </div>
+  )
+}
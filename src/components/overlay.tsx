@@ -0,0 +1,72 @@
+import { listen } from "@tauri-apps/api/event"
+import { createSignal, For, onCleanup, onMount, Show } from "solid-js"
+import { useAutoclickerStore } from "@/lib/autoclicker-store"
+import type { Channel, ClickerPhase, ClickStats } from "@/lib/bindings"
+import { getStats, getStatus } from "@/lib/commands"
+
+const channels: { value: Channel; label: string }[] = [
+  { value: "left", label: "L" },
+  { value: "right", label: "R" },
+]
+
+// The contents of the always-on-top overlay window. Rust shows the window only
+// while the clicker runs; this just renders what the click loop reports.
+export function Overlay() {
+  const store = useAutoclickerStore()
+  const [phase, setPhase] = createSignal<ClickerPhase>("idle")
+  const [stats, setStats] = createSignal<ClickStats | null>(null)
+
+  const active = (channel: Channel) => {
+    const current = phase()
+    return typeof current === "object" && current.clicking[channel]
+  }
+  const cps = () =>
+    channels.reduce(
+      (sum, { value }) => sum + (active(value) ? (stats()?.[value].measuredCps ?? 0) : 0),
+      0,
+    )
+  const sessionClicks = () =>
+    (stats()?.left.sessionClicks ?? 0) + (stats()?.right.sessionClicks ?? 0)
+
+  onMount(() => {
+    document.documentElement.style.background = "transparent"
+    document.body.style.background = "transparent"
+    const unlisten = [
+      listen<ClickerPhase>("clicker-state", (event) => setPhase(event.payload)),
+      listen<ClickStats>("clicker-stats", (event) => setStats(event.payload)),
+    ]
+    onCleanup(() => unlisten.forEach((pending) => pending.then((stop) => stop())))
+    getStatus().then((status) => setPhase(status.phase))
+    getStats().then(setStats)
+  })
+
+  return (
+    <div
+      class="flex h-screen w-screen select-none items-center justify-between gap-3 rounded-lg bg-black/80 px-3 text-white"
+      style={{ opacity: store.overlay.opacity }}
+    >
+      <div class="flex gap-1">
+        <For each={channels}>
+          {(channel) => (
+            <span
+              class={`flex h-6 w-6 items-center justify-center rounded text-xs font-semibold ${
+                active(channel.value) ? "bg-green-500 text-black" : "bg-white/10 text-white/50"
+              }`}
+            >
+              {channel.label}
+            </span>
+          )}
+        </For>
+      </div>
+      <Show
+        when={typeof phase() === "object"}
+        fallback={<span class="text-xs text-white/70">Listening</span>}
+      >
+        <div class="text-right font-mono leading-tight">
+          <div class="text-sm">{cps().toFixed(1)} CPS</div>
+          <div class="text-xs text-white/70">{sessionClicks()} clicks</div>
+        </div>
+      </Show>
+    </div>
+  )
+}
@@ -0,0 +1,135 @@
+import { invoke } from "@tauri-apps/api/core"
+import { Layers } from "lucide-solid"
+import { createSignal, For, Show } from "solid-js"
+import { Button } from "@/components/ui/button"
+import { Input } from "@/components/ui/input"
+import { Label } from "@/components/ui/label"
+import { useAutoclickerStore } from "@/lib/autoclicker-store"
+
+export function ProfileControl() {
+  const store = useAutoclickerStore()
+  const [name, setName] = createSignal("")
+  const [error, setError] = createSignal<string | null>(null)
+  const [recording, setRecording] = createSignal(false)
+
+  const run = async (command: string, args: Record<string, unknown>): Promise<boolean> => {
+    try {
+      await invoke(command, args)
+      setError(null)
+      return true
+    } catch (e) {
+      setError(String(e))
+      return false
+    }
+  }
+
+  const withName = async (command: string, args: Record<string, unknown>) => {
+    if (await run(command, args)) setName("")
+  }
+
+  const recordSwitchHotkey = async () => {
+    if (recording()) return
+    setRecording(true)
+    try {
+      const hotkey = await invoke<string | null>("capture_hotkey")
+      if (hotkey) await run("set_profile_hotkey", { name: store.activeProfile, hotkey })
+    } finally {
+      setRecording(false)
+    }
+  }
+
+  const switchHotkey = () => store.profiles[store.activeProfile]?.switchHotkey ?? ""
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <Layers class="h-4 w-4 text-muted-foreground" />
+          <Label for="profile-select" class="text-sm font-medium">
+            Profile
+          </Label>
+        </div>
+
+        <select
+          id="profile-select"
+          class="h-7 rounded-md border border-input bg-background px-2 text-xs"
+          value={store.activeProfile}
+          onChange={(e) => run("switch_profile", { name: e.currentTarget.value })}
+        >
+          <For each={Object.keys(store.profiles)}>
+            {(profile) => <option value={profile}>{profile}</option>}
+          </For>
+        </select>
+      </div>
+
+      <div class="flex items-center gap-2">
+        <Input
+          placeholder="Profile name"
+          value={name()}
+          onInput={(e) => setName(e.currentTarget.value)}
+          class="h-7 flex-1 text-xs"
+        />
+        <Button
+          variant="outline"
+          size="sm"
+          class="h-7 text-xs"
+          onClick={() => withName("create_profile", { name: name() })}
+        >
+          New
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          class="h-7 text-xs"
+          onClick={() => withName("clone_profile", { source: store.activeProfile, name: name() })}
+        >
+          Clone
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          class="h-7 text-xs"
+          onClick={() => withName("rename_profile", { name: store.activeProfile, newName: name() })}
+        >
+          Rename
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          class="h-7 text-xs"
+          onClick={() => run("delete_profile", { name: store.activeProfile })}
+        >
+          Delete
+        </Button>
+      </div>
+
+      <div class="flex items-center justify-between min-h-[28px]">
+        <Label class="text-xs font-medium text-muted-foreground">Switch hotkey</Label>
+        <div class="flex items-center gap-2">
+          <Show when={switchHotkey() && !recording()}>
+            <Button
+              variant="outline"
+              size="sm"
+              class="h-7 text-xs"
+              onClick={() => run("set_profile_hotkey", { name: store.activeProfile, hotkey: "" })}
+            >
+              Clear
+            </Button>
+          </Show>
+          <Button
+            variant={recording() ? "default" : "outline"}
+            size="sm"
+            class="h-7 min-w-[80px] text-xs"
+            onClick={recordSwitchHotkey}
+          >
+            {recording() ? "Press key..." : switchHotkey() || "None"}
+          </Button>
+        </div>
+      </div>
+
+      <Show when={error()}>
+        <p class="text-xs text-red-500">{error()}</p>
+      </Show>
+    </div>
+  )
+}
@@ -0,0 +1,82 @@
+import { ShieldAlert } from "lucide-solid"
+import { For } from "solid-js"
+import { Input } from "@/components/ui/input"
+import { Label } from "@/components/ui/label"
+import { Switch } from "@/components/ui/switch"
+import { type FailsafeCorner, useAutoclickerStore } from "@/lib/autoclicker-store"
+
+const corners: { value: FailsafeCorner; label: string }[] = [
+  { value: "off", label: "Off" },
+  { value: "topLeft", label: "Top left" },
+  { value: "topRight", label: "Top right" },
+  { value: "bottomLeft", label: "Bottom left" },
+  { value: "bottomRight", label: "Bottom right" },
+  { value: "any", label: "Any corner" },
+]
+
+export function SafetyControl() {
+  const store = useAutoclickerStore()
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <ShieldAlert class="h-4 w-4 text-muted-foreground" />
+          <Label for="failsafe-corner" class="text-sm font-medium">
+            Fail-safe corner
+          </Label>
+        </div>
+
+        <select
+          id="failsafe-corner"
+          class="h-7 rounded-md border border-input bg-background px-2 text-xs"
+          value={store.failsafeCorner}
+          onChange={(e) => store.setFailsafeCorner(e.currentTarget.value as FailsafeCorner)}
+        >
+          <For each={corners}>{(corner) => <option value={corner.value}>{corner.label}</option>}</For>
+        </select>
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Moving the cursor into this screen corner stops all clicking
+      </p>
+
+      <div class="flex items-center justify-between pt-2">
+        <Label for="mouse-move-threshold" class="text-sm font-medium">
+          Stop on mouse movement
+        </Label>
+        <div class="flex items-center gap-2">
+          <Input
+            id="mouse-move-threshold"
+            type="number"
+            min={0}
+            value={store.mouseMoveThreshold}
+            onInput={(e) => {
+              const pixels = Number.parseInt(e.currentTarget.value, 10)
+              store.setMouseMoveThreshold(!Number.isNaN(pixels) && pixels > 0 ? pixels : 0)
+            }}
+            class="h-7 w-20 text-xs"
+          />
+          <span class="text-xs text-muted-foreground">px</span>
+        </div>
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Stops active clicking when you move the mouse further than this from where it last clicked. 0 turns it off
+      </p>
+
+      <div class="flex items-center justify-between pt-2">
+        <Label for="resume-session-switch" class="text-sm font-medium">
+          Resume previous session
+        </Label>
+        <Switch
+          id="resume-session-switch"
+          checked={store.resumePreviousSession}
+          onCueChange={store.toggleResumePreviousSession}
+        />
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Listen for hotkeys again on launch if the app was listening when it closed. Clicking
+        never resumes by itself
+      </p>
+    </div>
+  )
+}
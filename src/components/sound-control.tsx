@@ -0,0 +1,126 @@
+import { open } from "@tauri-apps/plugin-dialog"
+import { FolderOpen, Play, Volume2, X } from "lucide-solid"
+import { createSignal, For, Show } from "solid-js"
+import { Button } from "@/components/ui/button"
+import { Label } from "@/components/ui/label"
+import { Slider } from "@/components/ui/slider"
+import { Switch } from "@/components/ui/switch"
+import { type Cue, useAutoclickerStore } from "@/lib/autoclicker-store"
+import { errorMessage, previewCue } from "@/lib/commands"
+
+type FileKey = "channelOnFile" | "channelOffFile" | "alertFile"
+
+const cues: { cue: Cue; file: FileKey; label: string }[] = [
+  { cue: "channelOn", file: "channelOnFile", label: "Channel on" },
+  { cue: "channelOff", file: "channelOffFile", label: "Channel off" },
+  { cue: "alert", file: "alertFile", label: "Stopped by fail-safe or error" },
+]
+
+const filters = [{ name: "Audio", extensions: ["wav", "ogg"] }]
+
+const fileName = (path: string) => path.split(/[\\/]/).pop() ?? path
+
+export function SoundControl() {
+  const store = useAutoclickerStore()
+  const [error, setError] = createSignal<string | null>(null)
+
+  const pickFile = async (file: FileKey) => {
+    const path = await open({ multiple: false, directory: false, filters })
+    if (path) store.setSounds({ [file]: path })
+  }
+
+  const preview = async (cue: Cue) => {
+    try {
+      await previewCue(cue)
+      setError(null)
+    } catch (e) {
+      setError(errorMessage(e))
+    }
+  }
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <Volume2 class="h-4 w-4 text-muted-foreground" />
+          <Label for="sounds-switch" class="text-sm font-medium">
+            Sound cues
+          </Label>
+        </div>
+        <Switch
+          id="sounds-switch"
+          checked={store.sounds.enabled}
+          onCueChange={() => store.setSounds({ enabled: !store.sounds.enabled })}
+        />
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Plays a short sound when a channel turns on or off and when clicking is stopped for you
+      </p>
+
+      <Show when={store.sounds.enabled}>
+        <div class="flex items-center justify-between pt-2">
+          <Label class="text-sm">Volume</Label>
+          <span class="text-xs font-mono text-muted-foreground">
+            {Math.round(store.sounds.volume * 100)}%
+          </span>
+        </div>
+        <Slider
+          value={[store.sounds.volume * 100]}
+          minValue={0}
+          maxValue={100}
+          step={5}
+          onChange={(values) => {
+            const value = Array.isArray(values) ? values[0] : values
+            store.setSounds({ volume: value / 100 })
+          }}
+          class="my-4"
+        />
+
+        <For each={cues}>
+          {(entry) => (
+            <div class="flex items-center justify-between gap-2">
+              <div class="min-w-0">
+                <p class="text-xs font-medium">{entry.label}</p>
+                <p class="truncate text-xs text-muted-foreground">
+                  {store.sounds[entry.file] ? fileName(store.sounds[entry.file]) : "Built-in tone"}
+                </p>
+              </div>
+              <div class="flex shrink-0 gap-1">
+                <Button
+                  variant="outline"
+                  size="sm"
+                  class="h-7 text-xs"
+                  onClick={() => preview(entry.cue)}
+                >
+                  <Play class="h-3 w-3" />
+                </Button>
+                <Button
+                  variant="outline"
+                  size="sm"
+                  class="h-7 text-xs"
+                  onClick={() => pickFile(entry.file)}
+                >
+                  <FolderOpen class="h-3 w-3" />
+                </Button>
+                <Show when={store.sounds[entry.file]}>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    class="h-7 text-xs"
+                    onClick={() => store.setSounds({ [entry.file]: "" })}
+                  >
+                    <X class="h-3 w-3" />
+                  </Button>
+                </Show>
+              </div>
+            </div>
+          )}
+        </For>
+      </Show>
+
+      <Show when={error()}>
+        <p class="text-xs text-red-500">{error()}</p>
+      </Show>
+    </div>
+  )
+}
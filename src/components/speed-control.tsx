@@ -6,15 +6,20 @@ import { Label } from "@/components/ui/label"
 import { Slider } from "@/components/ui/slider"
 import { Switch } from "@/components/ui/switch"
 import { useAutoclickerStore } from "@/lib/autoclicker-store"
+import type { Channel } from "@/lib/bindings"
 
 type SpeedMode = "cps" | "ms"
 
 export function SpeedControl() {
   const store = useAutoclickerStore()
   const [mode, setMode] = createSignal<SpeedMode>("cps")
+  const [channel, setChannel] = createSignal<Channel>("left")
+
+  const clickSpeed = () => store.channel(channel()).clickSpeed
+  const randomization = () => store.channel(channel()).randomization
 
   function getDisplayValue() {
-    return mode() === "cps" ? 1000 / store.clickSpeed : store.clickSpeed
+    return mode() === "cps" ? 1000 / clickSpeed() : clickSpeed()
   }
 
   function toMilliseconds(value: number) {
@@ -33,6 +38,25 @@ export function SpeedControl() {
   return (
     <div class="w-full max-w-md space-y-4">
       <div class="p-4 rounded-lg border border-border/50 bg-card space-y-6">
+        <div class="flex justify-center space-x-2">
+          <Button
+            variant={channel() === "left" ? "default" : "outline"}
+            size="sm"
+            class="flex-1"
+            onClick={() => setChannel("left")}
+          >
+            Left click
+          </Button>
+          <Button
+            variant={channel() === "right" ? "default" : "outline"}
+            size="sm"
+            class="flex-1"
+            onClick={() => setChannel("right")}
+          >
+            Right click
+          </Button>
+        </div>
+
         <div class="flex justify-center space-x-2">
           <Button
             variant={mode() === "cps" ? "default" : "outline"}
@@ -60,8 +84,8 @@ export function SpeedControl() {
               </Label>
               <div class="bg-muted/30 rounded px-2 py-1 text-sm font-mono text-muted-foreground">
                 {mode() === "cps"
-                  ? `${(1000 / store.clickSpeed).toFixed(1)} CPS (${store.clickSpeed.toFixed(1)}ms)`
-                  : `${store.clickSpeed.toFixed(1)}ms (${(1000 / store.clickSpeed).toFixed(1)} CPS)`}
+                  ? `${(1000 / clickSpeed()).toFixed(1)} CPS (${clickSpeed().toFixed(1)}ms)`
+                  : `${clickSpeed().toFixed(1)}ms (${(1000 / clickSpeed()).toFixed(1)} CPS)`}
               </div>
             </div>
 
@@ -72,7 +96,7 @@ export function SpeedControl() {
               step={step}
               onChange={(values) => {
                 const value = Array.isArray(values) ? values[0] : values
-                store.setClickSpeed(mode() === "cps" ? toMilliseconds(value) : value)
+                store.setClickSpeed(channel(), mode() === "cps" ? toMilliseconds(value) : value)
               }}
               class="my-6"
             />
@@ -86,9 +110,9 @@ export function SpeedControl() {
               onInput={(e) => {
                 const numValue = Number.parseFloat(e.currentTarget.value)
                 if (!Number.isNaN(numValue) && numValue > 0) {
-                  store.setClickSpeed(toMilliseconds(numValue))
+                  store.setClickSpeed(channel(), toMilliseconds(numValue))
                 } else {
-                  store.setClickSpeed(mode() === "cps" ? toMilliseconds(1.0) : 1.0)
+                  store.setClickSpeed(channel(), mode() === "cps" ? toMilliseconds(1.0) : 1.0)
                 }
               }}
               class="w-24"
@@ -97,6 +121,27 @@ export function SpeedControl() {
               {mode() === "cps" ? "clicks per second" : "milliseconds"}
             </span>
           </div>
+
+          <div class="flex items-center gap-2">
+            <Input
+              id="randomization-input"
+              type="number"
+              min={0}
+              max={100}
+              value={randomization()}
+              onInput={(e) => {
+                const percent = Number.parseFloat(e.currentTarget.value)
+                store.setRandomization(
+                  channel(),
+                  Number.isNaN(percent) ? 0 : Math.min(Math.max(percent, 0), 100),
+                )
+              }}
+              class="w-24"
+            />
+            <Label for="randomization-input" class="text-sm text-muted-foreground">
+              % random variation per click
+            </Label>
+          </div>
         </div>
       </div>
 
@@ -0,0 +1,54 @@
+import { listen } from "@tauri-apps/api/event"
+import { Activity } from "lucide-solid"
+import { type Accessor, createSignal, For, onCleanup, onMount } from "solid-js"
+import { Label } from "@/components/ui/label"
+import type { Channel, ChannelStats, ClickStats } from "@/lib/bindings"
+import { getStats } from "@/lib/commands"
+
+const channels: { value: Channel; label: string }[] = [
+  { value: "left", label: "Left" },
+  { value: "right", label: "Right" },
+]
+
+const ms = (value: number | null) => (value === null ? "–" : `${value.toFixed(1)} ms`)
+
+function ChannelRow(props: { label: string; stats: Accessor<ChannelStats | undefined> }) {
+  return (
+    <div class="space-y-1">
+      <div class="flex items-center justify-between">
+        <span class="text-sm font-medium">{props.label}</span>
+        <span class="text-sm font-mono">{(props.stats()?.measuredCps ?? 0).toFixed(1)} CPS</span>
+      </div>
+      <div class="grid grid-cols-3 gap-1 text-xs text-muted-foreground">
+        <span>Session {props.stats()?.sessionClicks ?? 0}</span>
+        <span>Total {props.stats()?.totalClicks ?? 0}</span>
+        <span>Missed {props.stats()?.missedDeadlines ?? 0}</span>
+        <span>Min {ms(props.stats()?.minIntervalMs ?? null)}</span>
+        <span>Avg {ms(props.stats()?.avgIntervalMs ?? null)}</span>
+        <span>Max {ms(props.stats()?.maxIntervalMs ?? null)}</span>
+      </div>
+    </div>
+  )
+}
+
+export function StatsPanel() {
+  const [stats, setStats] = createSignal<ClickStats>()
+
+  onMount(() => {
+    const unlisten = listen<ClickStats>("clicker-stats", (event) => setStats(event.payload))
+    onCleanup(() => unlisten.then((stop) => stop()))
+    getStats().then(setStats)
+  })
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center gap-2">
+        <Activity class="h-4 w-4 text-muted-foreground" />
+        <Label class="text-sm font-medium">Statistics</Label>
+      </div>
+      <For each={channels}>
+        {(channel) => <ChannelRow label={channel.label} stats={() => stats()?.[channel.value]} />}
+      </For>
+    </div>
+  )
+}
@@ -0,0 +1,112 @@
+import { invoke } from "@tauri-apps/api/core"
+import { open, save } from "@tauri-apps/plugin-dialog"
+import { FileDown, FileUp } from "lucide-solid"
+import { createSignal, For, Show } from "solid-js"
+import { Button } from "@/components/ui/button"
+import { Label } from "@/components/ui/label"
+import { useAutoclickerStore } from "@/lib/autoclicker-store"
+import type { ConflictStrategy, ImportSummary } from "@/lib/bindings"
+
+const filters = [
+  { name: "Settings", extensions: ["json", "toml"] },
+  { name: "All files", extensions: ["*"] },
+]
+
+function describe(summary: ImportSummary): string[] {
+  return [
+    ...(summary.settingsReplaced ? ["Global settings replaced"] : []),
+    ...summary.added.map((name) => `Added "${name}"`),
+    ...summary.overwritten.map((name) => `Overwrote "${name}"`),
+    ...Object.entries(summary.renamed).map(([from, to]) => `Imported "${from}" as "${to}"`),
+    ...summary.skipped.map((name) => `Skipped existing "${name}"`),
+  ]
+}
+
+export function TransferControl() {
+  const store = useAutoclickerStore()
+  const [onlyActive, setOnlyActive] = createSignal(false)
+  const [onConflict, setOnConflict] = createSignal<ConflictStrategy>("rename")
+  const [result, setResult] = createSignal<string[]>([])
+  const [error, setError] = createSignal<string | null>(null)
+
+  const exportSettings = async () => {
+    const profile = onlyActive() ? store.activeProfile : null
+    const path = await save({ defaultPath: `${profile ?? "autoclicker"}.json`, filters })
+    if (!path) return
+    try {
+      await invoke("export_settings", { path, profile })
+      setError(null)
+      setResult([`Exported to ${path}`])
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const importSettings = async () => {
+    const path = await open({ multiple: false, directory: false, filters })
+    if (!path) return
+    try {
+      const summary = await invoke<ImportSummary>("import_settings", {
+        path,
+        onConflict: onConflict(),
+      })
+      setError(null)
+      setResult(describe(summary))
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <input
+            id="export-active-only"
+            type="checkbox"
+            checked={onlyActive()}
+            onChange={(e) => setOnlyActive(e.currentTarget.checked)}
+          />
+          <Label for="export-active-only" class="text-xs font-medium">
+            Only the active profile
+          </Label>
+        </div>
+        <Button variant="outline" size="sm" class="h-7 text-xs" onClick={exportSettings}>
+          <FileDown class="h-3 w-3 mr-1" />
+          Export
+        </Button>
+      </div>
+
+      <div class="flex items-center justify-between">
+        <div class="flex items-center gap-2">
+          <Label for="import-conflict" class="text-xs font-medium">
+            Existing profiles
+          </Label>
+          <select
+            id="import-conflict"
+            class="h-7 rounded-md border border-input bg-background px-2 text-xs"
+            value={onConflict()}
+            onChange={(e) => setOnConflict(e.currentTarget.value as ConflictStrategy)}
+          >
+            <option value="rename">Keep both</option>
+            <option value="overwrite">Overwrite</option>
+            <option value="skip">Skip</option>
+          </select>
+        </div>
+        <Button variant="outline" size="sm" class="h-7 text-xs" onClick={importSettings}>
+          <FileUp class="h-3 w-3 mr-1" />
+          Import
+        </Button>
+      </div>
+
+      <Show when={error()}>
+        <p class="text-xs text-red-500">{error()}</p>
+      </Show>
+      <Show when={!error() && result().length > 0}>
+        <ul class="text-xs text-muted-foreground">
+          <For each={result()}>{(line) => <li>{line}</li>}</For>
+        </ul>
+      </Show>
+    </div>
+  )
+}
@@ -0,0 +1,46 @@
+import { AppWindow } from "lucide-solid"
+import { Label } from "@/components/ui/label"
+import { Switch } from "@/components/ui/switch"
+import { useAutoclickerStore } from "@/lib/autoclicker-store"
+
+export function WindowControl() {
+  const store = useAutoclickerStore()
+
+  return (
+    <div class="w-full max-w-md rounded-md border border-border/30 bg-background/50 p-3 space-y-2">
+      <div class="flex items-center gap-2">
+        <AppWindow class="h-4 w-4 text-muted-foreground" />
+        <Label class="text-sm font-medium">Window</Label>
+      </div>
+
+      <div class="flex items-center justify-between">
+        <Label for="minimize-to-tray-switch" class="text-sm">
+          Minimize to tray
+        </Label>
+        <Switch
+          id="minimize-to-tray-switch"
+          checked={store.minimizeToTray}
+          onCueChange={store.toggleMinimizeToTray}
+        />
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Closing or minimizing the window hides it to the tray icon. Use Quit in the tray menu to
+        exit
+      </p>
+
+      <div class="flex items-center justify-between pt-2">
+        <Label for="start-hidden-switch" class="text-sm">
+          Start hidden
+        </Label>
+        <Switch
+          id="start-hidden-switch"
+          checked={store.startHidden}
+          onCueChange={store.toggleStartHidden}
+        />
+      </div>
+      <p class="text-xs text-muted-foreground">
+        Launch with only the tray icon showing. The window still opens if there's no tray
+      </p>
+    </div>
+  )
+}
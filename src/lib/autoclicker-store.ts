@@ -1,49 +1,34 @@
 import { createSignal, onCleanup } from "solid-js"
 import { Store } from "tauri-store"
+import {
+  AUTOCLICKER_DEFAULTS,
+  AUTOCLICKER_STORE,
+  type AutoclickerSettings,
+  type Channel,
+  type FailsafeCorner,
+  type NotificationSettings,
+  type OverlaySettings,
+  type SoundSettings,
+} from "@/lib/bindings"
+import { setSpeed } from "@/lib/commands"
 
-interface AutoclickerState {
-  clickSpeed: number
-  holdMode: boolean
-  hotkeyLeft: string
-  hotkeyRight: string
-  [key: string]: unknown
-}
+export type { Cue, FailsafeCorner, OverlayPosition, Profile } from "@/lib/bindings"
 
-const store = new Store<AutoclickerState>("autoclicker", {
-  clickSpeed: 100.0,
-  holdMode: false,
-  hotkeyLeft: "F5",
-  hotkeyRight: "F6",
-})
+const store = new Store<AutoclickerSettings>(AUTOCLICKER_STORE, AUTOCLICKER_DEFAULTS)
 
-const initialState: AutoclickerState = {
-  clickSpeed: 100.0,
-  holdMode: false,
-  hotkeyLeft: "F5",
-  hotkeyRight: "F6",
-}
+const [state, setState] = createSignal<AutoclickerSettings>(AUTOCLICKER_DEFAULTS)
 
-const [state, setState] = createSignal<AutoclickerState>(initialState)
+const readState = () =>
+  Object.fromEntries(
+    Object.entries(AUTOCLICKER_DEFAULTS).map(([key, fallback]) => [
+      key,
+      store.get(key as keyof AutoclickerSettings) ?? fallback,
+    ]),
+  ) as AutoclickerSettings
 
-store.start().then(() => {
-  const currentState = {
-    clickSpeed: (store.get("clickSpeed") as number) ?? 100.0,
-    holdMode: (store.get("holdMode") as boolean) ?? false,
-    hotkeyLeft: (store.get("hotkeyLeft") as string) ?? "F5",
-    hotkeyRight: (store.get("hotkeyRight") as string) ?? "F6",
-  }
-  setState(currentState)
-})
+store.start().then(() => setState(readState()))
 
-const unsubscribe = store.subscribe(() => {
-  const currentState = {
-    clickSpeed: (store.get("clickSpeed") as number) ?? 100.0,
-    holdMode: (store.get("holdMode") as boolean) ?? false,
-    hotkeyLeft: (store.get("hotkeyLeft") as string) ?? "F5",
-    hotkeyRight: (store.get("hotkeyRight") as string) ?? "F6",
-  }
-  setState(currentState)
-})
+const unsubscribe = store.subscribe(() => setState(readState()))
 
 onCleanup(() => {
   unsubscribe()
@@ -53,9 +38,14 @@ export function useAutoclickerStore() {
   const currentState = state
 
   return {
-    get clickSpeed() {
-      return currentState().clickSpeed
+    get leftChannel() {
+      return currentState().leftChannel
+    },
+    get rightChannel() {
+      return currentState().rightChannel
     },
+    channel: (channel: Channel) =>
+      channel === "left" ? currentState().leftChannel : currentState().rightChannel,
     get holdMode() {
       return currentState().holdMode
     },
@@ -65,9 +55,43 @@ export function useAutoclickerStore() {
     get hotkeyRight() {
       return currentState().hotkeyRight
     },
-    setClickSpeed: (speed: number) => {
-      store.set("clickSpeed", speed)
-      store.save()
+    get panicHotkey() {
+      return currentState().panicHotkey
+    },
+    get failsafeCorner() {
+      return currentState().failsafeCorner
+    },
+    get mouseMoveThreshold() {
+      return currentState().mouseMoveThreshold
+    },
+    get resumePreviousSession() {
+      return currentState().resumePreviousSession
+    },
+    get minimizeToTray() {
+      return currentState().minimizeToTray
+    },
+    get startHidden() {
+      return currentState().startHidden
+    },
+    get overlay() {
+      return currentState().overlay
+    },
+    get sounds() {
+      return currentState().sounds
+    },
+    get notifications() {
+      return currentState().notifications
+    },
+    get profiles() {
+      return currentState().profiles
+    },
+    get activeProfile() {
+      return currentState().activeProfile
+    },
+    setClickSpeed: (channel: Channel, speed: number) => setSpeed(speed, channel),
+    setRandomization: (channel: Channel, randomization: number) => {
+      const timing = channel === "left" ? currentState().leftChannel : currentState().rightChannel
+      return setSpeed(timing.clickSpeed, channel, randomization)
     },
     toggleHoldMode: () => {
       const current = store.get("holdMode")
@@ -82,5 +106,41 @@ export function useAutoclickerStore() {
       store.set("hotkeyRight", hotkey)
       store.save()
     },
+    setPanicHotkey: (hotkey: string) => {
+      store.set("panicHotkey", hotkey)
+      store.save()
+    },
+    setFailsafeCorner: (corner: FailsafeCorner) => {
+      store.set("failsafeCorner", corner)
+      store.save()
+    },
+    setMouseMoveThreshold: (pixels: number) => {
+      store.set("mouseMoveThreshold", pixels)
+      store.save()
+    },
+    toggleResumePreviousSession: () => {
+      store.set("resumePreviousSession", !store.get("resumePreviousSession"))
+      store.save()
+    },
+    toggleMinimizeToTray: () => {
+      store.set("minimizeToTray", !store.get("minimizeToTray"))
+      store.save()
+    },
+    toggleStartHidden: () => {
+      store.set("startHidden", !store.get("startHidden"))
+      store.save()
+    },
+    setOverlay: (overlay: Partial<OverlaySettings>) => {
+      store.set("overlay", { ...currentState().overlay, ...overlay })
+      store.save()
+    },
+    setSounds: (sounds: Partial<SoundSettings>) => {
+      store.set("sounds", { ...currentState().sounds, ...sounds })
+      store.save()
+    },
+    setNotifications: (notifications: Partial<NotificationSettings>) => {
+      store.set("notifications", { ...currentState().notifications, ...notifications })
+      store.save()
+    },
   }
 }
@@ -0,0 +1,217 @@
+// Generated from the Rust types by `cargo test export_bindings`. Do not edit.
+
+export type FailsafeCorner = "off" | "topLeft" | "topRight" | "bottomLeft" | "bottomRight" | "any";
+
+export type ChannelTiming = { 
+/**
+ * Milliseconds between clicks.
+ */
+clickSpeed: number, 
+/**
+ * How far each interval may stray from `click_speed`, in percent.
+ */
+randomization: number, };
+
+export type OverlayPosition = "topLeft" | "topRight" | "bottomLeft" | "bottomRight";
+
+export type OverlaySettings = { enabled: boolean, position: OverlayPosition, 
+/**
+ * From 0.2 to 1.
+ */
+opacity: number, };
+
+export type Cue = "channelOn" | "channelOff" | "alert";
+
+export type SoundSettings = { enabled: boolean, 
+/**
+ * From 0 to 1.
+ */
+volume: number, 
+/**
+ * WAV or OGG file played when a channel turns on.
+ */
+channelOnFile: string, channelOffFile: string, 
+/**
+ * Played when a fail-safe, emergency stop or error stops the clicker.
+ */
+alertFile: string, };
+
+export type NotificationSettings = { enabled: boolean, 
+/**
+ * The fail-safe, the panic hotkey and taking over the mouse.
+ */
+safetyStops: boolean, 
+/**
+ * Input backend failures and crashed worker threads.
+ */
+errors: boolean, };
+
+export type Profile = { leftChannel: ChannelTiming, rightChannel: ChannelTiming, holdMode: boolean, hotkeyLeft: string, hotkeyRight: string, 
+/**
+ * Switches to this profile while the clicker is running, empty disables.
+ */
+switchHotkey: string, };
+
+export type AutoclickerSettings = { schemaVersion: number, leftChannel: ChannelTiming, rightChannel: ChannelTiming, holdMode: boolean, hotkeyLeft: string, hotkeyRight: string, panicHotkey: string, failsafeCorner: FailsafeCorner, 
+/**
+ * Pixels the cursor may move away from where it was at the last click
+ * before active channels stop, in both modes. 0 disables.
+ */
+mouseMoveThreshold: number, 
+/**
+ * Start listening again on launch if the clicker was running when the app
+ * last exited. Channels always start off.
+ */
+resumePreviousSession: boolean, 
+/**
+ * Closing or minimizing the window hides it to the tray instead.
+ */
+minimizeToTray: boolean, 
+/**
+ * Launch with only the tray icon showing.
+ */
+startHidden: boolean, overlay: OverlaySettings, sounds: SoundSettings, notifications: NotificationSettings, profiles: { [key in string]?: Profile }, activeProfile: string, };
+
+export type TempState = { isRunning: boolean, hotkeyLeftActive: boolean, hotkeyRightActive: boolean, };
+
+export type Channel = "left" | "right";
+
+export type Channels = { left: boolean, right: boolean, };
+
+export type ClickerPhase = "idle" | "armed" | { "clicking": Channels } | "stopping";
+
+export type StopReason = "user" | "emergencyStop" | "failsafe" | "userTookOver" | "backendError" | "workerCrashed" | "appExit";
+
+export type ClickerStatus = { phase: ClickerPhase, isRunning: boolean, leftActive: boolean, rightActive: boolean, holdMode: boolean, leftChannel: ChannelTiming, rightChannel: ChannelTiming, activeProfile: string, };
+
+export type ClickerErrorKind = "notRunning" | "noBackend" | "invalidSpeed" | "invalidRandomization" | "store";
+
+export type ClickerError = { kind: ClickerErrorKind, message: string, };
+
+export type BackendStatus = { "state": "connecting" } | { "state": "ready" } | { "state": "failed", error: string, 
+/**
+ * Failed attempts in a row.
+ */
+attempts: number, retryInMs: number, };
+
+export type ChannelStats = { 
+/**
+ * Clicks since the app started.
+ */
+totalClicks: number, 
+/**
+ * Clicks since the clicker was last started.
+ */
+sessionClicks: number, 
+/**
+ * Clicks per second actually performed over the last couple of seconds.
+ */
+measuredCps: number, minIntervalMs: number | null, maxIntervalMs: number | null, avgIntervalMs: number | null, 
+/**
+ * Clicks this session that came so late the next one was already due.
+ */
+missedDeadlines: number, };
+
+export type ClickStats = { left: ChannelStats, right: ChannelStats, };
+
+export type SessionRecord = { 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+startedAt: number, endedAt: number, profile: string, 
+/**
+ * The channels that clicked at least once.
+ */
+channels: Array<Channel>, clicks: number, stopReason: StopReason, };
+
+export type Worker = "clicker" | "hotkeys";
+
+export type WorkerCrash = { worker: Worker, 
+/**
+ * The panic message.
+ */
+message: string, 
+/**
+ * Restarts of this worker so far, including the one about to happen.
+ */
+restarts: number, };
+
+export type IssueKind = "unknownKey" | "modifierOnly" | "duplicate" | "subset" | "systemShortcut";
+
+export type IssueSeverity = "error" | "warning";
+
+export type HotkeyIssue = { kind: IssueKind, severity: IssueSeverity, bindings: Array<string>, message: string, };
+
+export type HotkeyValidation = { valid: boolean, issues: Array<HotkeyIssue>, };
+
+export type ConflictStrategy = "rename" | "overwrite" | "skip";
+
+export type ImportSummary = { added: Array<string>, overwritten: Array<string>, 
+/**
+ * Imported name -> name it was stored under.
+ */
+renamed: { [key in string]?: string }, skipped: Array<string>, settingsReplaced: boolean, };
+
+export const AUTOCLICKER_STORE = "autoclicker"
+export const TEMP_STORE = "temp"
+
+export const AUTOCLICKER_DEFAULTS: AutoclickerSettings = {
+  "schemaVersion": 3,
+  "leftChannel": {
+    "clickSpeed": 100.0,
+    "randomization": 0.0
+  },
+  "rightChannel": {
+    "clickSpeed": 100.0,
+    "randomization": 0.0
+  },
+  "holdMode": false,
+  "hotkeyLeft": "F5",
+  "hotkeyRight": "F6",
+  "panicHotkey": "Ctrl+Alt+Shift+Escape",
+  "failsafeCorner": "off",
+  "mouseMoveThreshold": 0,
+  "resumePreviousSession": false,
+  "minimizeToTray": false,
+  "startHidden": false,
+  "overlay": {
+    "enabled": false,
+    "position": "topRight",
+    "opacity": 0.85
+  },
+  "sounds": {
+    "enabled": false,
+    "volume": 0.5,
+    "channelOnFile": "",
+    "channelOffFile": "",
+    "alertFile": ""
+  },
+  "notifications": {
+    "enabled": false,
+    "safetyStops": true,
+    "errors": true
+  },
+  "profiles": {
+    "Default": {
+      "leftChannel": {
+        "clickSpeed": 100.0,
+        "randomization": 0.0
+      },
+      "rightChannel": {
+        "clickSpeed": 100.0,
+        "randomization": 0.0
+      },
+      "holdMode": false,
+      "hotkeyLeft": "F5",
+      "hotkeyRight": "F6",
+      "switchHotkey": ""
+    }
+  },
+  "activeProfile": "Default"
+}
+
+export const TEMP_DEFAULTS: TempState = {
+  "isRunning": false,
+  "hotkeyLeftActive": false,
+  "hotkeyRightActive": false
+}
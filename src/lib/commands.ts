@@ -0,0 +1,46 @@
+import { invoke } from "@tauri-apps/api/core"
+import type {
+  BackendStatus,
+  Channel,
+  ClickerError,
+  ClickerStatus,
+  ClickStats,
+  Cue,
+  SessionRecord,
+} from "@/lib/bindings"
+
+export type { ClickerError, ClickerErrorKind } from "@/lib/bindings"
+
+export const start = () => invoke<ClickerStatus>("start")
+
+export const stop = () => invoke<ClickerStatus>("stop")
+
+export const toggleChannel = (channel: Channel, active?: boolean) =>
+  invoke<ClickerStatus>("toggle_channel", { channel, active })
+
+export const setSpeed = (clickSpeed: number, channel?: Channel, randomization?: number) =>
+  invoke<ClickerStatus>("set_speed", { channel, clickSpeed, randomization })
+
+export const getStatus = () => invoke<ClickerStatus>("get_status")
+
+export const getBackendStatus = () => invoke<BackendStatus>("get_backend_status")
+
+export const getStats = () => invoke<ClickStats>("get_stats")
+
+export const getHistory = (limit?: number) => invoke<SessionRecord[]>("get_history", { limit })
+
+export const clearHistory = () => invoke<void>("clear_history")
+
+/** Plays the cue with the current sound settings, even while sounds are off. */
+export const previewCue = (cue: Cue) => invoke<void>("preview_cue", { cue })
+
+export const getRecentLogs = (lines?: number) => invoke<string[]>("get_recent_logs", { lines })
+
+/** Writes the bundle to `path` as JSON as well when given. */
+export const collectDiagnostics = (path?: string) =>
+  invoke<Record<string, unknown>>("collect_diagnostics", { path })
+
+export const errorMessage = (error: unknown) =>
+  typeof error === "object" && error !== null && "message" in error
+    ? String((error as ClickerError).message)
+    : String(error)
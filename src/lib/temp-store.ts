@@ -1,44 +1,23 @@
 import { createSignal, onCleanup } from "solid-js"
 import { Store } from "tauri-store"
+import { TEMP_DEFAULTS, TEMP_STORE, type TempState } from "@/lib/bindings"
+import { start, stop } from "@/lib/commands"
 
-interface TempState {
-  isRunning: boolean
-  hotkeyLeftActive: boolean
-  hotkeyRightActive: boolean
-  [key: string]: unknown
-}
+const store = new Store<TempState>(TEMP_STORE, TEMP_DEFAULTS)
 
-const store = new Store<TempState>("temp", {
-  isRunning: false,
-  hotkeyLeftActive: false,
-  hotkeyRightActive: false,
-})
+const [state, setState] = createSignal<TempState>(TEMP_DEFAULTS)
 
-const initialState: TempState = {
-  isRunning: false,
-  hotkeyLeftActive: false,
-  hotkeyRightActive: false,
-}
+const readState = () =>
+  Object.fromEntries(
+    Object.entries(TEMP_DEFAULTS).map(([key, fallback]) => [
+      key,
+      store.get(key as keyof TempState) ?? fallback,
+    ]),
+  ) as TempState
 
-const [state, setState] = createSignal<TempState>(initialState)
+store.start().then(() => setState(readState()))
 
-store.start().then(() => {
-  const currentState = {
-    isRunning: (store.get("isRunning") as boolean) ?? false,
-    hotkeyLeftActive: (store.get("hotkeyLeftActive") as boolean) ?? false,
-    hotkeyRightActive: (store.get("hotkeyRightActive") as boolean) ?? false,
-  }
-  setState(currentState)
-})
-
-const unsubscribe = store.subscribe(() => {
-  const currentState = {
-    isRunning: (store.get("isRunning") as boolean) ?? false,
-    hotkeyLeftActive: (store.get("hotkeyLeftActive") as boolean) ?? false,
-    hotkeyRightActive: (store.get("hotkeyRightActive") as boolean) ?? false,
-  }
-  setState(currentState)
-})
+const unsubscribe = store.subscribe(() => setState(readState()))
 
 onCleanup(() => {
   unsubscribe()
@@ -57,9 +36,6 @@ export function useTempStore() {
     get hotkeyRightActive() {
       return currentState().hotkeyRightActive
     },
-    toggleIsRunning: () => {
-      const current = store.get("isRunning")
-      store.set("isRunning", !current)
-    },
+    toggleIsRunning: () => (store.get("isRunning") ? stop() : start()),
   }
 }
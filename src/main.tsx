@@ -1,6 +1,8 @@
+import { getCurrentWindow } from "@tauri-apps/api/window"
 import { render } from "solid-js/web"
 import "./globals.css"
 import App from "./App"
+import { Overlay } from "./components/overlay"
 
 const root = document.getElementById("root")
 
@@ -10,4 +12,7 @@ if (import.meta.env.DEV && !(root instanceof HTMLElement)) {
   )
 }
 
-render(() => <App />, root!)
+// The overlay window loads the same page; see src-tauri/src/overlay.rs.
+const isOverlay = getCurrentWindow().label === "overlay"
+
+render(() => (isOverlay ? <Overlay /> : <App />), root!)